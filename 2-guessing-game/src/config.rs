@@ -0,0 +1,356 @@
+//! Command line options for the guessing game binary.
+
+use crate::{
+    difficulty::Difficulty,
+    hints::{Bands, Hints},
+    i18n::Lang,
+    protocol,
+    puzzle::{self, Puzzle, PuzzleKind, Session},
+    solver::StrategyKind,
+    text, Game, NumberRange,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fmt,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+/// What the binary was asked to do.
+#[derive(Debug, Default, PartialEq)]
+pub enum Command {
+    #[default]
+    Play,
+    /// `scores [list]`: show the high-score table, optionally only one `--difficulty`.
+    ListScores { difficulty: Option<Difficulty> },
+    /// `scores reset`: empty the high-score table.
+    ResetScores,
+    /// `solve`: let a `--strategy` (or every strategy) play `--games` games.
+    Solve {
+        strategy: Option<StrategyKind>,
+        games: u32,
+    },
+    /// `serve`: play one game per TCP client on `--bind` and `--port`.
+    Serve {
+        bind: String,
+        port: u16,
+        idle_timeout: Duration,
+    },
+    /// `stats`: report on every game in the `--history`.
+    Stats,
+    /// `replay verify <file>`: check that the current rules answer a recording identically.
+    VerifyReplay { path: PathBuf },
+    /// `replay play <file>`: show a recording again at `--speed`.
+    PlayReplay { path: PathBuf, speed: f64 },
+}
+
+/// How guesses are read and answered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+    /// Prompts and messages for a person at a terminal.
+    #[default]
+    Text,
+    /// One JSON object per line, see the `protocol` module.
+    Jsonl,
+}
+
+impl Protocol {
+    /// Plays `game` over `input` and `output` with this protocol's frontend.
+    pub fn run<P: Puzzle>(
+        self,
+        game: &mut Game<P>,
+        hints: Option<Hints>,
+        input: impl BufRead,
+        output: impl Write,
+    ) -> io::Result<()> {
+        match self {
+            Protocol::Text => text::run(game, hints, input, output),
+            Protocol::Jsonl => protocol::run(game, hints, input, output),
+        }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Protocol, String> {
+        match s {
+            "text" => Ok(Protocol::Text),
+            "jsonl" => Ok(Protocol::Jsonl),
+            _ => Err(format!("unknown protocol '{s}' (expected text or jsonl)")),
+        }
+    }
+}
+
+/// Options parsed from the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    pub command: Command,
+    /// Seed for the secret number; `None` draws it from the thread RNG.
+    pub seed: Option<u64>,
+    pub range: NumberRange,
+    /// Attempts budget; `None` plays until the number is found.
+    pub max_attempts: Option<u32>,
+    /// Name recorded in the high-score table.
+    pub player: String,
+    /// High-score file given with `--scores`; `None` uses `scores::default_path`.
+    pub scores_path: Option<PathBuf>,
+    /// Game history given with `--history`; `None` uses `history::default_path`.
+    pub history_path: Option<PathBuf>,
+    pub protocol: Protocol,
+    /// Hot/cold hint thresholds; `None` when `--hints` is off.
+    pub hints: Option<Bands>,
+    /// Names given with `--players`; empty for a single-player game.
+    pub players: Vec<String>,
+    /// With `--host-picks`, the first player picks the secret number and the others guess it.
+    pub host_picks: bool,
+    /// Play in the full-screen terminal UI instead of line by line.
+    pub tui: bool,
+    /// File to record the session to with `--record`.
+    pub record: Option<PathBuf>,
+    /// With `--cheating-host`, the number is only settled when the game is over, see
+    /// `puzzle::cheating`.
+    pub cheating_host: bool,
+    /// What to guess; everything but numbers only plays with the text or JSON lines protocol.
+    pub puzzle: PuzzleKind,
+    /// Language of the messages, from `--lang` or else `LANG`.
+    pub lang: Lang,
+    /// How long each guess may take with `--guess-time`, see `timer`.
+    pub guess_time: Option<Duration>,
+    /// How long the whole session may take with `--time-limit`; a speedrun's rounds share it.
+    pub time_limit: Option<Duration>,
+    /// Rounds to play back to back with `--speedrun`, timing each of them.
+    pub speedrun: Option<u32>,
+}
+
+impl Config {
+    /// Parses the arguments that follow the program name.
+    ///
+    /// `--min` and `--max` override the bounds of `--difficulty`, which defaults to normal.
+    /// `--limited` caps the attempts at what a binary search of the range needs (see
+    /// `PuzzleKind::limited_attempts` for the other puzzles), unless `--attempts` gives the budget
+    /// explicitly. `--hint-bands` implies `--hints`.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut subcommand = Vec::new();
+        let mut difficulty = None;
+        let mut min = None;
+        let mut max = None;
+        let mut limited = false;
+        let mut player = None;
+        let mut strategy = None;
+        let mut games = 1000;
+        let mut bind = "127.0.0.1".to_string();
+        let mut port = 7878;
+        let mut idle_timeout = 60;
+        let mut speed = 1.0;
+        let mut hints = false;
+        let mut bands = None;
+        let mut lang = None;
+        let mut guess_time = None;
+        let mut time_limit = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => config.seed = Some(parse_value(&arg, args.next())?),
+                "--difficulty" => difficulty = Some(parse_value(&arg, args.next())?),
+                "--min" => min = Some(parse_value(&arg, args.next())?),
+                "--max" => max = Some(parse_value(&arg, args.next())?),
+                "--limited" => limited = true,
+                "--attempts" => config.max_attempts = Some(parse_value(&arg, args.next())?),
+                "--name" => player = Some(parse_value(&arg, args.next())?),
+                "--scores" => config.scores_path = Some(parse_value(&arg, args.next())?),
+                "--history" => config.history_path = Some(parse_value(&arg, args.next())?),
+                "--protocol" => config.protocol = parse_value(&arg, args.next())?,
+                "--strategy" => strategy = Some(parse_value(&arg, args.next())?),
+                "--games" => games = parse_value(&arg, args.next())?,
+                "--hints" => hints = true,
+                "--hint-bands" => bands = Some(parse_value(&arg, args.next())?),
+                "--players" => {
+                    let names: String = parse_value(&arg, args.next())?;
+                    config.players = names
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect();
+                }
+                "--host-picks" => config.host_picks = true,
+                "--cheating-host" => config.cheating_host = true,
+                "--tui" => config.tui = true,
+                "--record" => config.record = Some(parse_value(&arg, args.next())?),
+                "--puzzle" => config.puzzle = parse_value(&arg, args.next())?,
+                "--lang" => lang = Some(parse_value(&arg, args.next())?),
+                "--guess-time" => guess_time = Some(parse_value(&arg, args.next())?),
+                "--time-limit" => time_limit = Some(parse_value(&arg, args.next())?),
+                "--speedrun" => config.speedrun = Some(parse_value(&arg, args.next())?),
+                "--speed" => speed = parse_value(&arg, args.next())?,
+                "--bind" => bind = parse_value(&arg, args.next())?,
+                "--port" => port = parse_value(&arg, args.next())?,
+                "--idle-timeout" => idle_timeout = parse_value(&arg, args.next())?,
+                _ if !arg.starts_with("--") => subcommand.push(arg),
+                _ => return Err(format!("unknown argument '{arg}'")),
+            }
+        }
+
+        let words: Vec<&str> = subcommand.iter().map(String::as_str).collect();
+        config.command = match words[..] {
+            [] => Command::Play,
+            ["scores"] | ["scores", "list"] => Command::ListScores { difficulty },
+            ["scores", "reset"] => Command::ResetScores,
+            ["solve"] => Command::Solve { strategy, games },
+            ["stats"] => Command::Stats,
+            ["replay", "verify", path] => Command::VerifyReplay { path: path.into() },
+            ["replay", "play", path] => Command::PlayReplay {
+                path: path.into(),
+                speed,
+            },
+            ["serve"] => Command::Serve {
+                bind,
+                port,
+                idle_timeout: Duration::from_secs(idle_timeout),
+            },
+            _ => return Err(format!("unknown command '{}'", words.join(" "))),
+        };
+
+        if config.puzzle != PuzzleKind::Number {
+            let puzzle = config.puzzle;
+            if difficulty.is_some() || min.is_some() || max.is_some() {
+                return Err(format!(
+                    "--difficulty, --min and --max don't apply to --puzzle {puzzle}"
+                ));
+            }
+            if config.tui || !config.players.is_empty() {
+                return Err(format!(
+                    "--puzzle {puzzle} can't be combined with --tui or --players"
+                ));
+            }
+            if matches!(config.command, Command::Solve { .. }) {
+                return Err("solve only plays --puzzle number".to_string());
+            }
+        }
+        if config.cheating_host {
+            if config.puzzle != PuzzleKind::Number {
+                return Err("--cheating-host only plays --puzzle number".to_string());
+            }
+            if config.tui || !config.players.is_empty() || hints || bands.is_some() {
+                return Err(
+                    "--cheating-host can't be combined with --tui, --players or --hints"
+                        .to_string(),
+                );
+            }
+        }
+        if (hints || bands.is_some())
+            && matches!(config.puzzle, PuzzleKind::Code | PuzzleKind::Word)
+        {
+            return Err(format!("--hints don't apply to --puzzle {}", config.puzzle));
+        }
+
+        let preset = difficulty.unwrap_or(Difficulty::Normal).range();
+        config.range = NumberRange::new(min.unwrap_or(preset.min()), max.unwrap_or(preset.max()))?;
+        if limited && config.max_attempts.is_none() {
+            config.max_attempts = Some(config.puzzle.limited_attempts(config.range));
+        }
+        if config.max_attempts == Some(0) {
+            return Err("--attempts must be at least 1".to_string());
+        }
+
+        if config.host_picks && config.players.len() < 2 {
+            return Err("--host-picks needs at least two --players".to_string());
+        }
+        if idle_timeout == 0 {
+            return Err("--idle-timeout must be at least 1 second".to_string());
+        }
+        if !(speed > 0.0 && speed.is_finite()) {
+            return Err("--speed must be a positive number".to_string());
+        }
+        if config.record.is_some() {
+            if config.tui || !config.players.is_empty() {
+                return Err("--record can't be combined with --tui or --players".to_string());
+            }
+            // a recording is only reproducible with a known seed.
+            config.seed = config.seed.or_else(|| Some(rand::random()));
+        }
+        if config.tui && (config.protocol != Protocol::Text || !config.players.is_empty()) {
+            return Err("--tui can't be combined with --protocol or --players".to_string());
+        }
+        if !config.players.is_empty() && config.protocol != Protocol::Text {
+            return Err("--players only works with the text protocol".to_string());
+        }
+        if guess_time.is_some() || time_limit.is_some() || config.speedrun.is_some() {
+            if config.command != Command::Play {
+                return Err(
+                    "--guess-time, --time-limit and --speedrun only apply to play".to_string(),
+                );
+            }
+            if config.tui || !config.players.is_empty() || config.record.is_some() {
+                return Err(
+                    "--guess-time, --time-limit and --speedrun can't be combined with --tui, --players or --record"
+                        .to_string(),
+                );
+            }
+        }
+        if guess_time == Some(0) || time_limit == Some(0) {
+            return Err("--guess-time and --time-limit must be at least 1 second".to_string());
+        }
+        config.guess_time = guess_time.map(Duration::from_secs);
+        config.time_limit = time_limit.map(Duration::from_secs);
+        match config.speedrun {
+            Some(0) => return Err("--speedrun must be at least 1 round".to_string()),
+            Some(_) if config.protocol != Protocol::Text => {
+                return Err("--speedrun only works with the text protocol".to_string())
+            }
+            _ => {}
+        }
+
+        if hints || bands.is_some() {
+            config.hints = Some(bands.unwrap_or_default());
+        }
+
+        config.lang = lang.or_else(Lang::from_env).unwrap_or_default();
+        config.player = player
+            .or_else(|| env::var("USER").ok())
+            .or_else(|| env::var("USERNAME").ok())
+            .unwrap_or_else(|| "anonymous".to_string());
+
+        Ok(config)
+    }
+}
+
+impl Config {
+    /// Starts a game of the configured puzzle, drawing the solution from `seed` when given and
+    /// from the thread RNG otherwise.
+    pub fn new_session(&self, seed: Option<u64>) -> Box<dyn Session> {
+        let rng: &mut dyn rand::RngCore = match seed {
+            Some(seed) => &mut crate::seeded_rng(seed),
+            None => &mut rand::thread_rng(),
+        };
+        puzzle::start(self, rng)
+    }
+
+    /// Starts a game with the configured range and attempts budget, drawing the secret number
+    /// from `seed` when given and from the thread RNG otherwise.
+    pub fn new_game(&self, seed: Option<u64>) -> Game {
+        let game = match seed {
+            Some(seed) => Game::from_rng(self.range, &mut crate::seeded_rng(seed)),
+            None => Game::new(self.range),
+        };
+        match self.max_attempts {
+            Some(max_attempts) => game.limit_attempts(max_attempts),
+            None => game,
+        }
+    }
+}
+
+fn parse_value<T>(flag: &str, value: Option<String>) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let value = value.ok_or_else(|| format!("{flag} needs a value"))?;
+    value
+        .parse()
+        .map_err(|err| format!("invalid value '{value}' for {flag}: {err}"))
+}
@@ -0,0 +1,64 @@
+//! Named presets for the range of the secret number.
+
+use crate::NumberRange;
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    pub fn range(self) -> NumberRange {
+        let (min, max) = match self {
+            Difficulty::Easy => (1, 10),
+            Difficulty::Normal => (1, 100),
+            Difficulty::Hard => (1, 10_000),
+            Difficulty::Insane => (-1_000_000, 1_000_000),
+        };
+        NumberRange::new(min, max).expect("preset ranges are valid")
+    }
+
+    /// Finds the preset that uses exactly `range`, if any.
+    pub fn from_range(range: NumberRange) -> Option<Difficulty> {
+        Difficulty::ALL.into_iter().find(|d| d.range() == range)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Insane => "insane",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Difficulty, String> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|d| d.name() == s)
+            .ok_or_else(|| {
+                format!("unknown difficulty '{s}' (expected easy, normal, hard or insane)")
+            })
+    }
+}
@@ -0,0 +1,121 @@
+//! Hot/cold hints: how far a guess is from the secret number and whether it got closer.
+
+use crate::{i18n, NumberRange};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// How close a guess is, relative to the size of the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Band {
+    Burning,
+    Warm,
+    Freezing,
+}
+
+/// How a guess compares with the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Trend {
+    Closer,
+    Farther,
+    Same,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hint {
+    pub band: Band,
+    /// `None` for the first guess.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend: Option<Trend>,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&(i18n::messages().hint)(*self))
+    }
+}
+
+/// Band thresholds as percentages of the range size: a guess is burning within `burning`
+/// percent of the secret number, warm within `warm` percent and freezing beyond that.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bands {
+    pub burning: f64,
+    pub warm: f64,
+}
+
+impl Default for Bands {
+    fn default() -> Bands {
+        Bands {
+            burning: 5.0,
+            warm: 20.0,
+        }
+    }
+}
+
+impl FromStr for Bands {
+    type Err = String;
+
+    /// Parses `"<burning>,<warm>"`, for example `"5,20"`.
+    fn from_str(s: &str) -> Result<Bands, String> {
+        let (burning, warm) = s
+            .split_once(',')
+            .ok_or_else(|| format!("expected '<burning>,<warm>' percentages, got '{s}'"))?;
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("invalid percentage '{value}'"))
+        };
+        let bands = Bands {
+            burning: parse(burning)?,
+            warm: parse(warm)?,
+        };
+
+        if !(0.0 < bands.burning && bands.burning < bands.warm && bands.warm <= 100.0) {
+            return Err("percentages must satisfy 0 < burning < warm <= 100".to_string());
+        }
+        Ok(bands)
+    }
+}
+
+/// Turns the distance of each guess into a `Hint`, remembering the previous distance.
+#[derive(Debug)]
+pub struct Hints {
+    bands: Bands,
+    range: NumberRange,
+    previous_distance: Option<u64>,
+}
+
+impl Hints {
+    pub fn new(range: NumberRange, bands: Bands) -> Hints {
+        Hints {
+            bands,
+            range,
+            previous_distance: None,
+        }
+    }
+
+    /// Hints at a guess that is `distance` away from the secret number.
+    pub fn hint(&mut self, distance: u64) -> Hint {
+        let percent = distance as f64 * 100.0 / self.range.size() as f64;
+        let band = if percent <= self.bands.burning {
+            Band::Burning
+        } else if percent <= self.bands.warm {
+            Band::Warm
+        } else {
+            Band::Freezing
+        };
+
+        let trend = self
+            .previous_distance
+            .map(|previous| match distance.cmp(&previous) {
+                Ordering::Less => Trend::Closer,
+                Ordering::Greater => Trend::Farther,
+                Ordering::Equal => Trend::Same,
+            });
+        self.previous_distance = Some(distance);
+
+        Hint { band, trend }
+    }
+}
@@ -0,0 +1,102 @@
+//! The history of every game played, won or not, for `stats`.
+//!
+//! Games are kept one JSON object per line in `history.jsonl`, next to the high-score table.
+//! Lines are only ever appended, each with a single write, so the file can grow for years
+//! without being rewritten.
+
+use crate::{difficulty::Difficulty, puzzle::PuzzleKind, scores, NumberRange};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Won,
+    Lost,
+    /// The input was closed before the game was over.
+    Abandoned,
+}
+
+/// One game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub puzzle: PuzzleKind,
+    #[serde(default)]
+    pub cheating_host: bool,
+    pub player: String,
+    /// The range of a number game.
+    pub min: i64,
+    pub max: i64,
+    pub max_attempts: Option<u32>,
+    pub outcome: Outcome,
+    pub attempts: u32,
+    pub duration_ms: u64,
+    /// The `--seed` the game was played with, if any.
+    pub seed: Option<u64>,
+    /// Every guess that counted, as given by `Session::guesses`.
+    pub guesses: Vec<Value>,
+}
+
+impl HistoryEntry {
+    /// The range of a number game; `None` for the other puzzles.
+    pub fn range(&self) -> Option<NumberRange> {
+        match self.puzzle {
+            PuzzleKind::Number => NumberRange::new(self.min, self.max).ok(),
+            _ => None,
+        }
+    }
+
+    /// The preset a number game was played on; `None` for a custom range or another puzzle.
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        self.range().and_then(Difficulty::from_range)
+    }
+
+    /// The first guess of a number game.
+    pub fn first_guess(&self) -> Option<i64> {
+        self.range()?;
+        self.guesses.first().and_then(Value::as_i64)
+    }
+}
+
+/// Where the history is kept when `--history` is not given.
+pub fn default_path() -> Option<PathBuf> {
+    scores::default_path().map(|path| path.with_file_name("history.jsonl"))
+}
+
+/// Reads every game, oldest first. A missing file is an empty history.
+pub fn load(path: &Path) -> io::Result<Vec<HistoryEntry>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|err| {
+                let message = format!("line {}: {err}", index + 1);
+                io::Error::new(io::ErrorKind::InvalidData, message)
+            })
+        })
+        .collect()
+}
+
+/// Adds `entry` at the end of the history at `path`.
+pub fn append(path: &Path, entry: &HistoryEntry) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
@@ -0,0 +1,187 @@
+//! Message catalogs for everything the game says to a player.
+//!
+//! Each language is a `Messages` value in its own module. A catalog is a struct rather than a
+//! file of keys, so a catalog that misses a message, or has one the others lack, does not
+//! compile; templates are functions so every language can order the values as it needs.
+//!
+//! Errors about the command line, diagnostics on stderr and the `solve` report stay in English.
+
+use crate::hints::Hint;
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fmt, io,
+    str::FromStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+mod en;
+mod pt;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Pt,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Pt];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Pt => "pt",
+        }
+    }
+
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Lang::En => &en::MESSAGES,
+            Lang::Pt => &pt::MESSAGES,
+        }
+    }
+
+    /// The language of the `LANG` environment variable, if it is one we have.
+    pub fn from_env() -> Option<Lang> {
+        env::var("LANG").ok()?.parse().ok()
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    /// Accepts a language code alone (`pt`) or a locale such as `pt_BR.UTF-8` or `pt-PT`.
+    fn from_str(s: &str) -> Result<Lang, String> {
+        let code = s.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        Lang::ALL
+            .into_iter()
+            .find(|lang| lang.code().eq_ignore_ascii_case(code))
+            .ok_or_else(|| {
+                let codes: Vec<&str> = Lang::ALL.iter().map(|lang| lang.code()).collect();
+                format!("unknown language '{s}' (expected {})", codes.join(" or "))
+            })
+    }
+}
+
+static LANG: AtomicU8 = AtomicU8::new(Lang::En as u8);
+
+/// Switches the language of `messages` for the whole process.
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    Lang::ALL[usize::from(LANG.load(Ordering::Relaxed))]
+}
+
+/// The catalog of the current language, English until `set_lang` is called.
+pub fn messages() -> &'static Messages {
+    lang().messages()
+}
+
+/// Every message of one language.
+pub struct Messages {
+    // playing
+    pub guess_the_number: &'static str,
+    pub you_have_attempts: fn(u32) -> String,
+    pub please_input_your_guess: &'static str,
+    pub no_more_input_goodbye: &'static str,
+    pub you_guessed: fn(&dyn fmt::Display) -> String,
+    pub too_small: &'static str,
+    pub too_big: &'static str,
+    pub you_win: &'static str,
+    pub out_of_range: &'static str,
+    pub hint: fn(Hint) -> String,
+    pub hint_label: &'static str,
+    pub attempts_left: fn(u32) -> String,
+    pub you_lose: fn(i64) -> String,
+    pub you_lose_answer: fn(&str) -> String,
+    pub score: fn(u64, u32, f64) -> String,
+    pub idle_goodbye: &'static str,
+
+    // other puzzles
+    pub guess_the_date: fn(&str, &str) -> String,
+    pub earlier: &'static str,
+    pub later: &'static str,
+    pub not_a_date: fn(&str) -> String,
+    pub date_out_of_range: fn(&str, &str) -> String,
+    pub guess_the_code: &'static str,
+    pub code_feedback: fn(u8, u8) -> String,
+    pub invalid_code: fn(&str) -> String,
+    pub guess_the_word: &'static str,
+    pub invalid_word: fn(&str) -> String,
+
+    // invalid guesses
+    pub empty_guess: &'static str,
+    pub not_a_number: fn(&str) -> String,
+    pub negative: &'static str,
+    pub overflow: &'static str,
+    pub out_of_range_between: fn(i64, i64) -> String,
+    pub no_more_input: &'static str,
+    pub read_failed: fn(&io::Error) -> String,
+
+    // several players
+    pub each_player_has_attempts: fn(u32) -> String,
+    pub player_input_your_guess: fn(&str) -> String,
+    pub player_guessed: fn(&str, i64) -> String,
+    pub player_wins: fn(&str) -> String,
+    pub nobody_wins: fn(i64) -> String,
+    pub leaderboard: &'static str,
+    pub leaderboard_attempts: &'static str,
+    pub found_it: &'static str,
+    pub away: fn(u64) -> String,
+    pub no_guesses: &'static str,
+    pub pick_secret: fn(&str, i64, i64) -> String,
+
+    // time limits and speedruns
+    pub seconds_per_guess: fn(u64) -> String,
+    pub seconds_in_total: fn(u64) -> String,
+    pub guess_time_up: &'static str,
+    pub time_up: &'static str,
+    pub speedrun_round: fn(u32, u32) -> String,
+    pub split: fn(u32, f64, f64) -> String,
+    pub speedrun_done: fn(u32, f64) -> String,
+    pub fastest_split: fn(u32, f64) -> String,
+    pub speedrun_over: fn(u32, u32) -> String,
+
+    // high scores
+    pub no_high_scores: &'static str,
+    pub high_scores_reset: &'static str,
+    /// Column headers: rank, player, difficulty, attempts, time, score, seed.
+    pub score_columns: [&'static str; 7],
+
+    // stats
+    pub no_games_yet: &'static str,
+    pub stats_games: fn(u32, u32, u32, u32) -> String,
+    pub cheating_host: &'static str,
+    pub stats_attempts_heading: &'static str,
+    /// Label, games, wins, mean and median attempts, the last two already formatted.
+    pub stats_group: fn(&str, u32, u32, &str, &str) -> String,
+    pub stats_limits_heading: &'static str,
+    /// Label, attempt limit, wins, games and win rate in percent.
+    pub stats_limit: fn(&str, u32, u32, u32, f64) -> String,
+    pub stats_fastest_heading: &'static str,
+    /// Rank, player, label, attempts and seconds.
+    pub stats_fastest: fn(usize, &str, &str, u32, f64) -> String,
+    pub stats_first_guesses_heading: &'static str,
+    pub stats_players_heading: &'static str,
+    /// Player, wins, mean attempts, mean bound, and how far the mean is above the bound.
+    pub stats_player: fn(&str, u32, f64, f64, f64) -> String,
+
+    // full-screen UI
+    pub new_game_hint: &'static str,
+    pub keys_help: &'static str,
+    pub number_line: &'static str,
+    pub still_possible: fn(i64, i64) -> String,
+    pub history: &'static str,
+    pub attempts: &'static str,
+    pub attempts_of: fn(u32, u32) -> String,
+    pub attempts_bound: fn(u32, u32) -> String,
+}
@@ -0,0 +1,135 @@
+use super::Messages;
+use crate::hints::{Band, Trend};
+
+pub static MESSAGES: Messages = Messages {
+    guess_the_number: "Guess the number!",
+    you_have_attempts: |attempts| format!("You have {attempts} attempts."),
+    please_input_your_guess: "Please input your guess",
+    no_more_input_goodbye: "No more input, goodbye!",
+    you_guessed: |guess| format!("You guessed: {guess}"),
+    too_small: "Too small!",
+    too_big: "Too big!",
+    you_win: "You win!",
+    out_of_range: "Out of range!",
+    hint: |hint| {
+        let band = match hint.band {
+            Band::Burning => "burning",
+            Band::Warm => "warm",
+            Band::Freezing => "freezing",
+        };
+        match hint.trend {
+            Some(Trend::Closer) => format!("{band}, closer than before"),
+            Some(Trend::Farther) => format!("{band}, farther than before"),
+            Some(Trend::Same) => format!("{band}, as far as before"),
+            None => band.to_string(),
+        }
+    },
+    hint_label: "Hint",
+    attempts_left: |left| format!("{left} attempts left"),
+    you_lose: |secret| format!("You lose! The secret number was {secret}"),
+    you_lose_answer: |answer| format!("You lose! The answer was {answer}"),
+    score: |score, attempts, secs| format!("Score: {score} ({attempts} attempts in {secs:.1}s)"),
+    idle_goodbye: "Idle for too long, goodbye!",
+
+    guess_the_date: |first, last| {
+        format!("Guess the date!\nIt is between {first} and {last}; type it as YYYY-MM-DD.")
+    },
+    earlier: "Earlier!",
+    later: "Later!",
+    not_a_date: |input| format!("'{input}' is not a date like 2024-02-29"),
+    date_out_of_range: |first, last| {
+        format!("Out of range! The date is between {first} and {last}")
+    },
+    guess_the_code: "Guess the code!\n\
+        Four pegs out of r g b y o p (red, green, blue, yellow, orange, purple), \
+        colors may repeat. Type them like rgby.",
+    code_feedback: |exact, partial| {
+        format!("{exact} in the right place, {partial} in the wrong place")
+    },
+    invalid_code: |input| format!("'{input}' is not four of the letters r g b y o p"),
+    guess_the_word: "Guess the word!\n\
+        It has five letters. 🟩 right place, 🟨 wrong place, ⬛ not in the word.",
+    invalid_word: |input| format!("'{input}' is not a word of five letters"),
+
+    empty_guess: "Type a number before pressing enter",
+    not_a_number: |input| format!("'{input}' is not a number"),
+    negative: "The number can't be negative",
+    overflow: "That number has too many digits to be a guess",
+    out_of_range_between: |min, max| format!("Out of range! The number is between {min} and {max}"),
+    no_more_input: "No more input",
+    read_failed: |err| format!("Failed to read your guess: {err}"),
+
+    each_player_has_attempts: |attempts| format!("Each player has {attempts} attempts."),
+    player_input_your_guess: |player| format!("{player}, please input your guess"),
+    player_guessed: |player, guess| format!("{player} guessed: {guess}"),
+    player_wins: |player| format!("{player} wins!"),
+    nobody_wins: |secret| format!("Nobody wins! The secret number was {secret}"),
+    leaderboard: "Leaderboard",
+    leaderboard_attempts: "attempts",
+    found_it: "found it",
+    away: |distance| format!("{distance} away"),
+    no_guesses: "no guesses",
+    pick_secret: |host, min, max| {
+        format!("{host}, pick the secret number between {min} and {max}: ")
+    },
+
+    seconds_per_guess: |secs| format!("You have {secs} seconds for each guess."),
+    seconds_in_total: |secs| format!("You have {secs} seconds in total."),
+    guess_time_up: "Too slow, time's up for this guess!",
+    time_up: "Time's up!",
+    speedrun_round: |round, rounds| format!("Round {round} of {rounds}"),
+    split: |round, split, total| format!("Split {round}: {split:.1}s (total {total:.1}s)"),
+    speedrun_done: |rounds, total| format!("Speedrun complete: {rounds} rounds in {total:.1}s"),
+    fastest_split: |round, split| format!("Fastest split: round {round} in {split:.1}s"),
+    speedrun_over: |won, rounds| format!("Speedrun over after {won} of {rounds} rounds"),
+
+    no_high_scores: "No high scores yet",
+    high_scores_reset: "High scores reset",
+    score_columns: [
+        "rank",
+        "player",
+        "difficulty",
+        "attempts",
+        "time",
+        "score",
+        "seed",
+    ],
+
+    no_games_yet: "No games played yet",
+    stats_games: |games, won, lost, abandoned| {
+        format!("games: {games} (won: {won}, lost: {lost}, abandoned: {abandoned})")
+    },
+    cheating_host: "cheating host",
+    stats_attempts_heading: "attempts of won games",
+    stats_group: |label, games, won, mean, median| {
+        format!("  {label:<24} {games:>5} games {won:>5} won   mean {mean:>6}   median {median:>6}")
+    },
+    stats_limits_heading: "win rate under attempt limits",
+    stats_limit: |label, max_attempts, wins, games, rate| {
+        format!("  {label:<24} {max_attempts:>3} attempts {wins:>5} / {games:<5} {rate:>5.1}%")
+    },
+    stats_fastest_heading: "fastest wins",
+    stats_fastest: |rank, player, label, attempts, secs| {
+        format!("  {rank}. {player:<16} {label:<24} {attempts:>3} attempts {secs:>7.1}s")
+    },
+    stats_first_guesses_heading: "first guesses, by where they fell in the range",
+    stats_players_heading: "mean attempts of won games against the binary search bound",
+    stats_player: |player, wins, mean, bound, gap| {
+        // a gap that shows as 0.00 is on the bound, neither above nor below it.
+        let gap = match gap {
+            gap if gap.abs() < 0.005 => "on the bound".to_string(),
+            gap if gap > 0.0 => format!("{gap:.2} above"),
+            gap => format!("{:.2} below", -gap),
+        };
+        format!("  {player:<16} {wins:>5} won   mean {mean:>6.2}   bound {bound:>6.2}   {gap}")
+    },
+
+    new_game_hint: "Ctrl-N for a new game",
+    keys_help: "Enter: guess   Ctrl-N: new game   Esc: quit",
+    number_line: "Number line",
+    still_possible: |low, high| format!("still possible: {low}..={high}"),
+    history: "History",
+    attempts: "Attempts",
+    attempts_of: |attempts, max| format!("{attempts} / {max} attempts"),
+    attempts_bound: |attempts, bound| format!("{attempts} attempts (binary search needs {bound})"),
+};
@@ -0,0 +1,144 @@
+use super::Messages;
+use crate::hints::{Band, Trend};
+
+pub static MESSAGES: Messages = Messages {
+    guess_the_number: "Adivinhe o número!",
+    you_have_attempts: |attempts| format!("Você tem {attempts} tentativas."),
+    please_input_your_guess: "Digite o seu palpite",
+    no_more_input_goodbye: "Fim da entrada, até logo!",
+    you_guessed: |guess| format!("Você chutou: {guess}"),
+    too_small: "Muito baixo!",
+    too_big: "Muito alto!",
+    you_win: "Você venceu!",
+    out_of_range: "Fora do intervalo!",
+    hint: |hint| {
+        let band = match hint.band {
+            Band::Burning => "pelando",
+            Band::Warm => "morno",
+            Band::Freezing => "congelando",
+        };
+        match hint.trend {
+            Some(Trend::Closer) => format!("{band}, mais perto que antes"),
+            Some(Trend::Farther) => format!("{band}, mais longe que antes"),
+            Some(Trend::Same) => format!("{band}, tão longe quanto antes"),
+            None => band.to_string(),
+        }
+    },
+    hint_label: "Dica",
+    attempts_left: |left| format!("Restam {left} tentativas"),
+    you_lose: |secret| format!("Você perdeu! O número secreto era {secret}"),
+    you_lose_answer: |answer| format!("Você perdeu! A resposta era {answer}"),
+    score: |score, attempts, secs| {
+        format!("Pontuação: {score} ({attempts} tentativas em {secs:.1}s)")
+    },
+    idle_goodbye: "Inativo por muito tempo, até logo!",
+
+    guess_the_date: |first, last| {
+        format!("Adivinhe a data!\nEla está entre {first} e {last}; digite como AAAA-MM-DD.")
+    },
+    earlier: "Antes!",
+    later: "Depois!",
+    not_a_date: |input| format!("'{input}' não é uma data como 2024-02-29"),
+    date_out_of_range: |first, last| {
+        format!("Fora do intervalo! A data está entre {first} e {last}")
+    },
+    guess_the_code: "Adivinhe o código!\n\
+        Quatro pinos de seis cores, que podem se repetir, digitadas pela inicial em inglês: \
+        r = vermelho, g = verde, b = azul, y = amarelo, o = laranja, p = roxo. \
+        Digite como rgby.",
+    code_feedback: |exact, partial| format!("{exact} no lugar certo, {partial} no lugar errado"),
+    invalid_code: |input| {
+        format!(
+            "'{input}' não são quatro das letras r g b y o p \
+            (vermelho, verde, azul, amarelo, laranja, roxo)"
+        )
+    },
+    guess_the_word: "Adivinhe a palavra (em inglês)!\n\
+        Ela tem cinco letras. 🟩 lugar certo, 🟨 lugar errado, ⬛ fora da palavra.",
+    invalid_word: |input| format!("'{input}' não é uma palavra de cinco letras"),
+
+    empty_guess: "Digite um número antes de apertar enter",
+    not_a_number: |input| format!("'{input}' não é um número"),
+    negative: "O número não pode ser negativo",
+    overflow: "Esse número tem dígitos demais para ser um palpite",
+    out_of_range_between: |min, max| {
+        format!("Fora do intervalo! O número está entre {min} e {max}")
+    },
+    no_more_input: "Fim da entrada",
+    read_failed: |err| format!("Não foi possível ler o seu palpite: {err}"),
+
+    each_player_has_attempts: |attempts| format!("Cada jogador tem {attempts} tentativas."),
+    player_input_your_guess: |player| format!("{player}, digite o seu palpite"),
+    player_guessed: |player, guess| format!("{player} chutou: {guess}"),
+    player_wins: |player| format!("{player} venceu!"),
+    nobody_wins: |secret| format!("Ninguém venceu! O número secreto era {secret}"),
+    leaderboard: "Classificação",
+    leaderboard_attempts: "tentativas",
+    found_it: "acertou",
+    away: |distance| format!("a {distance} do número"),
+    no_guesses: "nenhum palpite",
+    pick_secret: |host, min, max| format!("{host}, escolha o número secreto entre {min} e {max}: "),
+
+    seconds_per_guess: |secs| format!("Você tem {secs} segundos para cada chute."),
+    seconds_in_total: |secs| format!("Você tem {secs} segundos no total."),
+    guess_time_up: "Devagar demais, o tempo deste chute acabou!",
+    time_up: "O tempo acabou!",
+    speedrun_round: |round, rounds| format!("Rodada {round} de {rounds}"),
+    split: |round, split, total| format!("Parcial {round}: {split:.1}s (total {total:.1}s)"),
+    speedrun_done: |rounds, total| format!("Speedrun completo: {rounds} rodadas em {total:.1}s"),
+    fastest_split: |round, split| format!("Parcial mais rápida: rodada {round} em {split:.1}s"),
+    speedrun_over: |won, rounds| format!("Fim do speedrun depois de {won} de {rounds} rodadas"),
+
+    no_high_scores: "Nenhuma pontuação ainda",
+    high_scores_reset: "Pontuações apagadas",
+    score_columns: [
+        "pos",
+        "jogador",
+        "dificuldade",
+        "tentativas",
+        "tempo",
+        "pontos",
+        "semente",
+    ],
+
+    no_games_yet: "Nenhum jogo ainda",
+    stats_games: |games, won, lost, abandoned| {
+        format!("jogos: {games} (ganhos: {won}, perdidos: {lost}, abandonados: {abandoned})")
+    },
+    cheating_host: "anfitrião trapaceiro",
+    stats_attempts_heading: "tentativas dos jogos ganhos",
+    stats_group: |label, games, won, mean, median| {
+        format!(
+            "  {label:<24} {games:>5} jogos {won:>5} ganhos   média {mean:>6}   mediana {median:>6}"
+        )
+    },
+    stats_limits_heading: "taxa de vitória com limite de tentativas",
+    stats_limit: |label, max_attempts, wins, games, rate| {
+        format!("  {label:<24} {max_attempts:>3} tentativas {wins:>5} / {games:<5} {rate:>5.1}%")
+    },
+    stats_fastest_heading: "vitórias mais rápidas",
+    stats_fastest: |rank, player, label, attempts, secs| {
+        format!("  {rank}. {player:<16} {label:<24} {attempts:>3} tentativas {secs:>7.1}s")
+    },
+    stats_first_guesses_heading: "primeiros chutes, por onde caíram no intervalo",
+    stats_players_heading: "média de tentativas dos jogos ganhos contra o limite da busca binária",
+    stats_player: |player, wins, mean, bound, gap| {
+        let gap = match gap {
+            gap if gap.abs() < 0.005 => "no limite".to_string(),
+            gap if gap > 0.0 => format!("{gap:.2} acima"),
+            gap => format!("{:.2} abaixo", -gap),
+        };
+        format!("  {player:<16} {wins:>5} ganhos   média {mean:>6.2}   limite {bound:>6.2}   {gap}")
+    },
+
+    new_game_hint: "Ctrl-N para um novo jogo",
+    keys_help: "Enter: chutar   Ctrl-N: novo jogo   Esc: sair",
+    number_line: "Reta numérica",
+    still_possible: |low, high| format!("ainda possível: {low}..={high}"),
+    history: "Histórico",
+    attempts: "Tentativas",
+    attempts_of: |attempts, max| format!("{attempts} / {max} tentativas"),
+    attempts_bound: |attempts, bound| {
+        format!("{attempts} tentativas (a busca binária precisa de {bound})")
+    },
+};
@@ -0,0 +1,268 @@
+//! Reading and validating a guess typed by a person.
+//!
+//! Besides plain decimals, guesses may use `_` separators (`1_000`), a `0x`, `0o` or `0b` prefix
+//! (`0x1F`), a sign and surrounding whitespace.
+
+use crate::{i18n, puzzle::Puzzle, NumberRange};
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, Read},
+    num::IntErrorKind,
+};
+
+/// Why a line could not be used as a guess.
+#[derive(Debug)]
+pub enum GuessError {
+    Empty,
+    NotANumber(String),
+    /// A negative number for a range that has none.
+    Negative,
+    /// Too large, in either direction, to be held by a guess.
+    Overflow,
+    OutOfRange {
+        min: i64,
+        max: i64,
+    },
+    /// Not a guess for the puzzle being played; the message says what would be.
+    Invalid(String),
+    /// The input was closed.
+    Eof,
+    Io(io::Error),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = i18n::messages();
+        match self {
+            GuessError::Empty => f.write_str(messages.empty_guess),
+            GuessError::NotANumber(input) => f.write_str(&(messages.not_a_number)(input)),
+            GuessError::Negative => f.write_str(messages.negative),
+            GuessError::Overflow => f.write_str(messages.overflow),
+            GuessError::OutOfRange { min, max } => {
+                f.write_str(&(messages.out_of_range_between)(*min, *max))
+            }
+            GuessError::Invalid(message) => f.write_str(message),
+            GuessError::Eof => f.write_str(messages.no_more_input),
+            GuessError::Io(err) => f.write_str(&(messages.read_failed)(err)),
+        }
+    }
+}
+
+impl Error for GuessError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GuessError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GuessError {
+    fn from(err: io::Error) -> GuessError {
+        GuessError::Io(err)
+    }
+}
+
+/// Parses `input` as a guess inside `range`.
+pub fn parse_guess(input: &str, range: NumberRange) -> Result<i64, GuessError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(GuessError::Empty);
+    }
+    let not_a_number = || GuessError::NotANumber(trimmed.to_string());
+
+    let (negative, unsigned) = match trimmed.as_bytes()[0] {
+        b'-' => (true, &trimmed[1..]),
+        b'+' => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x" | "0X") => (16, &unsigned[2..]),
+        Some("0o" | "0O") => (8, &unsigned[2..]),
+        Some("0b" | "0B") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+    // separators may go between digits, but not in front of them; `from_str_radix` would also
+    // take a second sign, as in `++5` or `0x+5`.
+    if digits.starts_with(['_', '+', '-']) {
+        return Err(not_a_number());
+    }
+    let digits = digits.replace('_', "");
+
+    let magnitude = u64::from_str_radix(&digits, radix).map_err(|err| match err.kind() {
+        IntErrorKind::PosOverflow => GuessError::Overflow,
+        _ => not_a_number(),
+    })?;
+    let guess = if negative {
+        0i64.checked_sub_unsigned(magnitude)
+    } else {
+        i64::try_from(magnitude).ok()
+    }
+    .ok_or(GuessError::Overflow)?;
+
+    if guess < 0 && range.min() >= 0 {
+        return Err(GuessError::Negative);
+    }
+    if !range.contains(guess) {
+        return Err(GuessError::OutOfRange {
+            min: range.min(),
+            max: range.max(),
+        });
+    }
+    Ok(guess)
+}
+
+/// Reads one line from `input` and parses it as a guess for `puzzle`.
+pub fn read_guess<P: Puzzle>(input: &mut impl BufRead, puzzle: &P) -> Result<P::Guess, GuessError> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(GuessError::Eof);
+    }
+    puzzle.parse_guess(&line)
+}
+
+/// Hands out the input one line at a time, for a `LineReader`.
+pub trait LineSource {
+    /// The next line with its `\n`, or nothing at the end of the input.
+    fn next_line(&mut self) -> io::Result<Vec<u8>>;
+}
+
+/// A `BufRead` over the lines of a `LineSource`, which is only asked for a line once the
+/// previous one has been read.
+pub struct LineReader<S> {
+    source: S,
+    line: Vec<u8>,
+    consumed: usize,
+}
+
+impl<S: LineSource> LineReader<S> {
+    pub fn new(source: S) -> LineReader<S> {
+        LineReader {
+            source,
+            line: Vec::new(),
+            consumed: 0,
+        }
+    }
+}
+
+impl<S: LineSource> Read for LineReader<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<S: LineSource> BufRead for LineReader<S> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.consumed == self.line.len() {
+            self.line = self.source.next_line()?;
+            self.consumed = 0;
+        }
+        Ok(&self.line[self.consumed..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.consumed = (self.consumed + amt).min(self.line.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::{
+        cheating::CheatingHost,
+        code::{Code, CodePuzzle, Color},
+    };
+
+    fn range(min: i64, max: i64) -> NumberRange {
+        NumberRange::new(min, max).unwrap()
+    }
+
+    #[test]
+    fn parses_every_form_of_number() {
+        let cases = [
+            ("42", 42),
+            ("  42\n", 42),
+            ("+42", 42),
+            ("-42", -42),
+            ("1_000", 1_000),
+            ("0x1F", 31),
+            ("0X1f", 31),
+            ("-0x1F", -31),
+            ("0o17", 15),
+            ("0b1010", 10),
+            ("9223372036854775807", i64::MAX),
+            ("-9223372036854775808", i64::MIN),
+        ];
+        for (input, expected) in cases {
+            let guess = parse_guess(input, range(i64::MIN, i64::MAX));
+            assert_eq!(guess.ok(), Some(expected), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_guess() {
+        let small = range(1, 100);
+        let signed = range(-10, 10);
+        let not_a_number = |input: &str| GuessError::NotANumber(input.to_string());
+        let cases = [
+            ("", small, GuessError::Empty),
+            ("  \n", small, GuessError::Empty),
+            ("abc", small, not_a_number("abc")),
+            ("++5", small, not_a_number("++5")),
+            ("-+5", signed, not_a_number("-+5")),
+            ("+-5", signed, not_a_number("+-5")),
+            ("0x+5", small, not_a_number("0x+5")),
+            ("_1", small, not_a_number("_1")),
+            ("0x_1F", small, not_a_number("0x_1F")),
+            ("0x", small, not_a_number("0x")),
+            ("0b102", small, not_a_number("0b102")),
+            (" 1 2 ", small, not_a_number("1 2")),
+            ("-5", small, GuessError::Negative),
+            ("9223372036854775808", small, GuessError::Overflow),
+            ("-9223372036854775809", signed, GuessError::Overflow),
+            ("101", small, GuessError::OutOfRange { min: 1, max: 100 }),
+            ("-11", signed, GuessError::OutOfRange { min: -10, max: 10 }),
+        ];
+        for (input, range, expected) in cases {
+            // `GuessError` holds an `io::Error`, so it has no `PartialEq`.
+            let err = parse_guess(input, range).expect_err(input);
+            assert_eq!(format!("{err:?}"), format!("{expected:?}"), "{input:?}");
+        }
+    }
+
+    struct Failing;
+
+    impl io::Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    #[test]
+    fn reads_a_line_as_a_guess() {
+        let host = CheatingHost::new(range(1, 100));
+        assert_eq!(
+            read_guess(&mut "0x1F\nrest".as_bytes(), &host).ok(),
+            Some(31)
+        );
+        assert!(matches!(
+            read_guess(&mut "".as_bytes(), &host),
+            Err(GuessError::Eof)
+        ));
+        assert!(matches!(
+            read_guess(&mut io::BufReader::new(Failing), &host),
+            Err(GuessError::Io(_))
+        ));
+
+        let code = CodePuzzle::with_secret(Code([Color::Red; 4]));
+        assert!(matches!(
+            read_guess(&mut "xyz\n".as_bytes(), &code),
+            Err(GuessError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+//! The rules of the guessing game, free of any terminal I/O.
+//!
+//! `Game` keeps the secret number and answers each guess with a `GuessOutcome`, so the binary,
+//! bots and tests all play by exactly the same rules. Other things to guess, such as dates or
+//! words, plug into the same `Game` through the `puzzle::Puzzle` trait.
+//!
+//! The secret number can be drawn from any `rand::RngCore`. Use `seeded_rng` to get a
+//! reproducible game: a given seed always yields the same secret number, and seeds are stable
+//! across releases of this crate (changing that is treated as a breaking change).
+
+pub mod config;
+pub mod difficulty;
+pub mod hints;
+pub mod history;
+pub mod i18n;
+pub mod input;
+pub mod multiplayer;
+pub mod protocol;
+pub mod puzzle;
+pub mod replay;
+pub mod score;
+pub mod scores;
+pub mod server;
+pub mod solver;
+pub mod stats;
+pub mod text;
+pub mod timer;
+pub mod tui;
+
+use puzzle::{Feedback, NumberPuzzle, Puzzle};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt};
+
+/// Returns the RNG used for `--seed`.
+///
+/// ChaCha8 is used instead of `StdRng` because its output is guaranteed not to change between
+/// versions of `rand`.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// The inclusive range the secret number is drawn from; `min` is always below `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberRange {
+    min: i64,
+    max: i64,
+}
+
+impl NumberRange {
+    pub fn new(min: i64, max: i64) -> Result<NumberRange, String> {
+        if min >= max {
+            return Err(format!("min ({min}) must be below max ({max})"));
+        }
+        Ok(NumberRange { min, max })
+    }
+
+    pub fn min(&self) -> i64 {
+        self.min
+    }
+
+    pub fn max(&self) -> i64 {
+        self.max
+    }
+
+    pub fn contains(&self, number: i64) -> bool {
+        (self.min..=self.max).contains(&number)
+    }
+
+    /// How many numbers the range holds.
+    pub fn size(&self) -> u128 {
+        (self.max as i128 - self.min as i128 + 1) as u128
+    }
+
+    /// The most guesses a binary search needs to find any number in the range, which is
+    /// `ceil(log2(size + 1))`.
+    pub fn optimal_attempts(&self) -> u32 {
+        u128::BITS - self.size().leading_zeros()
+    }
+}
+
+impl Default for NumberRange {
+    fn default() -> NumberRange {
+        NumberRange { min: 1, max: 100 }
+    }
+}
+
+impl fmt::Display for NumberRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..={}", self.min, self.max)
+    }
+}
+
+/// The answer the game gives to a single guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuessOutcome {
+    TooSmall,
+    TooBig,
+    Correct,
+    /// The guess is outside the game's range; it does not count as an attempt.
+    OutOfRange,
+}
+
+impl From<Ordering> for GuessOutcome {
+    fn from(ordering: Ordering) -> GuessOutcome {
+        match ordering {
+            Ordering::Less => GuessOutcome::TooSmall,
+            Ordering::Greater => GuessOutcome::TooBig,
+            Ordering::Equal => GuessOutcome::Correct,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    Playing,
+    Won,
+    /// The attempts budget ran out, or the game was given up, before the secret number was found.
+    Lost,
+}
+
+/// A single round: a puzzle, by default a secret number, and the guesses made against it so far.
+#[derive(Debug)]
+pub struct Game<P: Puzzle = NumberPuzzle> {
+    puzzle: P,
+    /// The guesses that counted as attempts, in order.
+    guesses: Vec<P::Guess>,
+    max_attempts: Option<u32>,
+    state: GameState,
+}
+
+impl<P: Puzzle> Game<P> {
+    pub fn from_puzzle(puzzle: P) -> Game<P> {
+        Game {
+            puzzle,
+            guesses: Vec::new(),
+            max_attempts: None,
+            state: GameState::Playing,
+        }
+    }
+
+    /// Ends the game as lost once `max_attempts` wrong guesses have been made.
+    pub fn limit_attempts(mut self, max_attempts: u32) -> Game<P> {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Checks `guess` against the puzzle; a correct guess wins the game and running out of
+    /// attempts loses it. Guesses made after the game is over are answered but not counted.
+    pub fn submit_guess(&mut self, guess: P::Guess) -> P::Feedback {
+        let feedback = self.puzzle.check(&guess);
+        if !feedback.counts() || self.is_over() {
+            return feedback;
+        }
+        self.guesses.push(guess);
+
+        if feedback.is_correct() {
+            self.state = GameState::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.state = GameState::Lost;
+        }
+        if self.is_over() {
+            self.puzzle.commit();
+        }
+        feedback
+    }
+
+    /// Ends a game that is still being played as lost.
+    pub fn give_up(&mut self) {
+        if !self.is_over() {
+            self.state = GameState::Lost;
+            self.puzzle.commit();
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.state != GameState::Playing
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn guesses(&self) -> &[P::Guess] {
+        &self.guesses
+    }
+
+    /// `None` when the game has no attempts budget.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn puzzle(&self) -> &P {
+        &self.puzzle
+    }
+
+    /// Reveals the solution, but only once the game is over.
+    pub fn solution(&self) -> Option<P::Guess> {
+        self.is_over().then(|| self.puzzle.solution())
+    }
+}
+
+impl Game {
+    /// Starts a game with a random secret number in `range`.
+    pub fn new(range: NumberRange) -> Game {
+        Game::from_rng(range, &mut rand::thread_rng())
+    }
+
+    /// Starts a game whose secret number is drawn from `rng`.
+    pub fn from_rng<R: RngCore + ?Sized>(range: NumberRange, rng: &mut R) -> Game {
+        // seeds were first drawn as `u32`, and sampling a wider type draws other numbers, so
+        // ranges that fit in a `u32` keep being drawn as one for their seeds to stay the same.
+        let secret_number = match (u32::try_from(range.min), u32::try_from(range.max)) {
+            (Ok(min), Ok(max)) => rng.gen_range(min..=max).into(),
+            _ => rng.gen_range(range.min..=range.max),
+        };
+        Game::with_secret(range, secret_number)
+    }
+
+    /// Starts a game with a known secret number, for bots and tests.
+    ///
+    /// Panics if `secret_number` is outside `range`.
+    pub fn with_secret(range: NumberRange, secret_number: i64) -> Game {
+        assert!(
+            range.contains(secret_number),
+            "secret number {secret_number} is outside {range}"
+        );
+        Game::from_puzzle(NumberPuzzle {
+            range,
+            secret_number,
+        })
+    }
+
+    /// How far `guess` is from the secret number, for hot/cold hints.
+    pub fn distance(&self, guess: i64) -> u64 {
+        guess.abs_diff(self.puzzle.secret_number)
+    }
+
+    /// Reveals the secret number, but only once the game is over.
+    pub fn secret_number(&self) -> Option<i64> {
+        self.solution()
+    }
+
+    pub fn range(&self) -> NumberRange {
+        self.puzzle.range
+    }
+}
+
+impl Default for Game {
+    fn default() -> Game {
+        Game::new(NumberRange::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(range: NumberRange, seed: u64) -> i64 {
+        Game::from_rng(range, &mut seeded_rng(seed))
+            .puzzle
+            .secret_number
+    }
+
+    #[test]
+    fn ranges_that_fit_a_u32_are_drawn_as_one() {
+        for (min, max) in [(1, 100), (0, 10), (1, 10_000), (0, u32::MAX as i64)] {
+            let range = NumberRange::new(min, max).unwrap();
+            for seed in 0..50 {
+                let drawn: u32 = seeded_rng(seed).gen_range(min as u32..=max as u32);
+                assert_eq!(
+                    secret(range, seed),
+                    drawn as i64,
+                    "{range} with seed {seed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn seeds_always_draw_the_same_secrets() {
+        // these are what every release has drawn; a different number here breaks old seeds.
+        let cases = [
+            (1, 100, 42, 23),
+            (1, 100, 7, 15),
+            (1, 100, 0, 66),
+            (50, 500, 42, 151),
+            (50, 500, 7, 113),
+            (-1_000_000, 1_000_000, 42, 363_793),
+            (-1_000_000, 1_000_000, 7, -684_408),
+        ];
+        for (min, max, seed, expected) in cases {
+            let range = NumberRange::new(min, max).unwrap();
+            assert_eq!(secret(range, seed), expected, "{range} with seed {seed}");
+        }
+    }
+}
@@ -1,36 +1,490 @@
-use rand::Rng;
-use std::{cmp::Ordering, io};
+use guessing_game::{
+    config::{Command, Config, Protocol},
+    difficulty::Difficulty,
+    history::{self, HistoryEntry, Outcome},
+    i18n,
+    input::{self, GuessError, LineReader},
+    multiplayer::Match,
+    puzzle::PuzzleKind,
+    replay::{self, Recording},
+    score,
+    scores::{self, ScoreEntry},
+    server,
+    solver::{self, StrategyKind},
+    stats::Stats,
+    timer::TimedInput,
+    tui, Game, GameState, GuessOutcome, NumberRange,
+};
+use std::{
+    env,
+    io::{self, BufRead, BufReader},
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 fn main() {
-    println!("Guess the number!");
+    let config = Config::build(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+    i18n::set_lang(config.lang);
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
-    // println!("The secret number is: {secret_number}");
+    match config.command {
+        Command::Play => play(&config),
+        Command::Serve {
+            ref bind,
+            port,
+            idle_timeout,
+        } => {
+            let address = format!("{bind}:{port}");
+            serve(&address, idle_timeout, config)
+        }
+        Command::ListScores { difficulty } => list_scores(&config, difficulty),
+        Command::Solve { strategy, games } => solve(&config, strategy, games),
+        Command::Stats => stats(&config),
+        Command::VerifyReplay { ref path } => verify_replay(path),
+        Command::PlayReplay { ref path, speed } => {
+            let recording = load_recording(path);
+            if let Err(err) = recording.play(speed, io::stdout().lock()) {
+                eprintln!("Failed to replay {}: {err}", path.display());
+                process::exit(1);
+            }
+        }
+        Command::ResetScores => {
+            let path = scores_path(&config);
+            if let Err(err) = scores::reset(&path) {
+                eprintln!("Could not reset {}: {err}", path.display());
+                process::exit(1);
+            }
+            println!("{}", i18n::messages().high_scores_reset);
+        }
+    }
+}
 
-    loop {
-        println!("Please input your guess");
-        let mut guess = String::new();
+fn play(config: &Config) {
+    if !config.players.is_empty() {
+        return play_match(config);
+    }
+    if config.tui {
+        let rounds = tui::run(config).unwrap_or_else(|err| {
+            eprintln!("Terminal UI error: {err}");
+            process::exit(1);
+        });
+        for round in rounds {
+            let played = Played {
+                seed: round.seed,
+                state: round.game.state(),
+                attempts: round.game.attempts(),
+                guesses: round
+                    .game
+                    .guesses()
+                    .iter()
+                    .map(|&guess| guess.into())
+                    .collect(),
+                elapsed: round.elapsed,
+            };
+            record_game(config, &played);
+        }
+        return;
+    }
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+    let messages = i18n::messages();
+    let mut input: Box<dyn BufRead> = if config.guess_time.is_some() || config.time_limit.is_some()
+    {
+        let stdin = BufReader::new(io::stdin());
+        let timed = TimedInput::new(stdin, config.guess_time, config.time_limit);
+        Box::new(LineReader::new(timed))
+    } else {
+        Box::new(io::stdin().lock())
+    };
+    if config.protocol == Protocol::Text {
+        if let Some(guess_time) = config.guess_time {
+            println!("{}", (messages.seconds_per_guess)(guess_time.as_secs()));
+        }
+        if let Some(time_limit) = config.time_limit {
+            println!("{}", (messages.seconds_in_total)(time_limit.as_secs()));
+        }
+    }
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("Type a number");
-                continue;
-            }
+    match config.speedrun {
+        Some(rounds) => speedrun(config, rounds, &mut *input),
+        None => {
+            play_round(config, config.seed, &mut *input);
+        }
+    }
+}
+
+/// Plays `rounds` games back to back, reporting the split time of every round, until one of
+/// them is not won.
+fn speedrun(config: &Config, rounds: u32, input: &mut dyn BufRead) {
+    let messages = i18n::messages();
+    let mut splits = Vec::new();
+
+    for round in 1..=rounds {
+        if round > 1 {
+            println!();
+        }
+        println!("{}", (messages.speedrun_round)(round, rounds));
+        // every round of a seeded run gets a number of its own, the same on every run.
+        let seed = config
+            .seed
+            .map(|seed| seed.wrapping_add(u64::from(round - 1)));
+        let (state, split) = play_round(config, seed, input);
+        if state != GameState::Won {
+            println!("\n{}", (messages.speedrun_over)(round - 1, rounds));
+            return;
+        }
+
+        splits.push(split);
+        let total: Duration = splits.iter().sum();
+        println!(
+            "{}",
+            (messages.split)(round, split.as_secs_f64(), total.as_secs_f64())
+        );
+    }
+
+    let total: Duration = splits.iter().sum();
+    println!(
+        "\n{}",
+        (messages.speedrun_done)(rounds, total.as_secs_f64())
+    );
+    if let Some((index, fastest)) = splits.iter().enumerate().min_by_key(|(_, split)| **split) {
+        println!(
+            "{}",
+            (messages.fastest_split)(index as u32 + 1, fastest.as_secs_f64())
+        );
+    }
+}
+
+/// Plays one game of `config` over `input`, records it and returns how it ended and how long it
+/// took.
+fn play_round(
+    config: &Config,
+    seed: Option<u64>,
+    input: &mut dyn BufRead,
+) -> (GameState, Duration) {
+    let mut session = config.new_session(seed);
+    let started = Instant::now();
+
+    let mut output = io::stdout().lock();
+    let result = match (&config.record, seed) {
+        (Some(path), Some(seed)) => {
+            replay::record(config, seed, &mut *session, input, output, path)
+        }
+        _ => session.play(input, &mut output),
+    };
+    if let Err(err) = result {
+        eprintln!("Failed to play: {err}");
+        process::exit(1);
+    }
+    let elapsed = started.elapsed();
+    let state = session.state();
+    if config.protocol == Protocol::Jsonl {
+        return (state, elapsed);
+    }
+    let played = Played {
+        seed,
+        state,
+        attempts: session.attempts(),
+        guesses: session.guesses(),
+        elapsed,
+    };
+    record_game(config, &played);
+    (state, elapsed)
+}
+
+/// A game that is over, or that was left unfinished, in text or in the terminal UI.
+struct Played {
+    seed: Option<u64>,
+    state: GameState,
+    attempts: u32,
+    guesses: Vec<serde_json::Value>,
+    elapsed: Duration,
+}
+
+/// Adds the game to the history, and a won number game to the high scores with its score.
+fn record_game(config: &Config, played: &Played) {
+    record_history(config, played);
+    // scores compare attempts against a binary search of the range, which only numbers have,
+    // and against a cheating host no game could have been shorter.
+    if config.puzzle != PuzzleKind::Number || config.cheating_host {
+        return;
+    }
+
+    if played.state == GameState::Won {
+        let range = config.range;
+        let score = score::score(range, played.attempts, played.elapsed);
+        let messages = i18n::messages();
+        println!(
+            "{}",
+            (messages.score)(score, played.attempts, played.elapsed.as_secs_f64())
+        );
+
+        let entry = ScoreEntry {
+            player: config.player.clone(),
+            min: range.min(),
+            max: range.max(),
+            attempts: played.attempts,
+            duration_ms: played.elapsed.as_millis() as u64,
+            seed: played.seed,
+            score,
         };
-        println!("You guessed: {guess}");
+        let path = scores_path(config);
+        if let Err(err) = scores::record(&path, entry) {
+            eprintln!("Could not save your score to {}: {err}", path.display());
+        }
+    }
+}
+
+/// Adds the game to the history for `stats`, unless no guess was made.
+fn record_history(config: &Config, played: &Played) {
+    if played.attempts == 0 {
+        return;
+    }
+    let entry = HistoryEntry {
+        puzzle: config.puzzle,
+        cheating_host: config.cheating_host,
+        player: config.player.clone(),
+        min: config.range.min(),
+        max: config.range.max(),
+        max_attempts: config.max_attempts,
+        outcome: match played.state {
+            GameState::Won => Outcome::Won,
+            GameState::Lost => Outcome::Lost,
+            GameState::Playing => Outcome::Abandoned,
+        },
+        attempts: played.attempts,
+        duration_ms: played.elapsed.as_millis() as u64,
+        seed: played.seed,
+        guesses: played.guesses.clone(),
+    };
+    let path = history_path(config);
+    if let Err(err) = history::append(&path, &entry) {
+        eprintln!("Could not save the game to {}: {err}", path.display());
+    }
+}
+
+fn play_match(config: &Config) {
+    let mut players = config.players.clone();
+    let range = config.range;
+
+    let game = if config.host_picks {
+        let host = players.remove(0);
+        Game::with_secret(range, pick_secret(&host, range))
+    } else {
+        match config.seed {
+            Some(seed) => Game::from_rng(range, &mut guessing_game::seeded_rng(seed)),
+            None => Game::new(range),
+        }
+    };
+    let mut game = Match::new(game, players, config.max_attempts);
+    let messages = i18n::messages();
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
+    println!("{}", messages.guess_the_number);
+    if let Some(max_attempts) = config.max_attempts {
+        println!("{}", (messages.each_player_has_attempts)(max_attempts));
+    }
+
+    while !game.is_over() {
+        let player = game.current_player().name.clone();
+        println!("{}", (messages.player_input_your_guess)(&player));
+        let guess = match input::read_guess(&mut io::stdin().lock(), game.game().puzzle()) {
+            Ok(guess) => guess,
+            Err(GuessError::Eof) => {
+                println!("{}", messages.no_more_input_goodbye);
                 break;
             }
+            Err(err @ GuessError::Io(_)) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+            Err(err) => {
+                println!("{err}");
+                continue;
+            }
+        };
+        println!("{}", (messages.player_guessed)(&player, guess));
+
+        let message = match game.submit_guess(guess) {
+            GuessOutcome::TooSmall => messages.too_small.to_string(),
+            GuessOutcome::TooBig => messages.too_big.to_string(),
+            GuessOutcome::Correct => (messages.player_wins)(&player),
+            GuessOutcome::OutOfRange => (messages.out_of_range_between)(range.min(), range.max()),
+        };
+        println!("{message}");
+    }
+
+    if game.winner().is_none() {
+        if let Some(secret_number) = game.game().secret_number() {
+            println!("{}", (messages.nobody_wins)(secret_number));
         }
     }
+
+    println!("\n{}", messages.leaderboard);
+    for (rank, player) in game.leaderboard().iter().enumerate() {
+        let closest = match player.best_distance {
+            Some(0) => messages.found_it.to_string(),
+            Some(distance) => (messages.away)(distance),
+            None => messages.no_guesses.to_string(),
+        };
+        println!(
+            "{:>2}. {:<16} {:>3} {}  {closest}",
+            rank + 1,
+            player.name,
+            player.attempts,
+            messages.leaderboard_attempts
+        );
+    }
+}
+
+/// Asks `host` for the secret number without echoing it, so the other players cannot see it.
+fn pick_secret(host: &str, range: NumberRange) -> i64 {
+    loop {
+        let prompt = (i18n::messages().pick_secret)(host, range.min(), range.max());
+        // without a terminal to hide the input (e.g. when piped), fall back to a plain line.
+        let secret = rpassword::prompt_password(&prompt).or_else(|_| {
+            println!("{prompt}");
+            let mut line = String::new();
+            io::stdin().read_line(&mut line).map(|_| line)
+        });
+        let secret = secret.unwrap_or_else(|err| {
+            eprintln!("Failed to read the secret number: {err}");
+            process::exit(1);
+        });
+
+        match input::parse_guess(&secret, range) {
+            Ok(secret) => return secret,
+            Err(err) => println!("{err}"),
+        }
+    }
+}
+
+fn list_scores(config: &Config, difficulty: Option<Difficulty>) {
+    let path = scores_path(config);
+    let entries = scores::load(&path).unwrap_or_else(|err| {
+        eprintln!("Could not read {}: {err}", path.display());
+        process::exit(1);
+    });
+    let entries: Vec<_> = entries
+        .into_iter()
+        .filter(|entry| difficulty.is_none() || entry.difficulty() == difficulty)
+        .collect();
+
+    if entries.is_empty() {
+        println!("{}", i18n::messages().no_high_scores);
+        return;
+    }
+
+    let [rank, player, difficulty, attempts, time, score, seed] = i18n::messages().score_columns;
+    println!(
+        "{rank:>4}  {player:<16} {difficulty:<11} {attempts:>10} {time:>8} {score:>8} {seed:>20}"
+    );
+    for (rank, entry) in entries.iter().enumerate() {
+        let difficulty = match entry.difficulty() {
+            Some(difficulty) => difficulty.to_string(),
+            None => format!("{}..={}", entry.min, entry.max),
+        };
+        let seed = entry.seed.map(|seed| seed.to_string()).unwrap_or_default();
+        println!(
+            "{:>4}  {:<16} {:<11} {:>10} {:>7.1}s {:>8} {:>20}",
+            rank + 1,
+            entry.player,
+            difficulty,
+            entry.attempts,
+            entry.duration_ms as f64 / 1000.0,
+            entry.score,
+            seed
+        );
+    }
+}
+
+fn stats(config: &Config) {
+    let path = history_path(config);
+    let entries = history::load(&path).unwrap_or_else(|err| {
+        eprintln!("Could not read {}: {err}", path.display());
+        process::exit(1);
+    });
+    if entries.is_empty() {
+        println!("{}", i18n::messages().no_games_yet);
+        return;
+    }
+    print!("{}", Stats::new(&entries));
+}
+
+fn solve(config: &Config, strategy: Option<StrategyKind>, games: u32) {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    println!("range: {}, seed: {seed}", config.range);
+    if config.cheating_host {
+        println!("against a cheating host, so every game is a worst case");
+    }
+
+    let strategies = match strategy {
+        Some(strategy) => vec![strategy],
+        None => StrategyKind::ALL.to_vec(),
+    };
+    for strategy in strategies {
+        let mut rng = guessing_game::seeded_rng(seed);
+        let report = solver::benchmark(
+            strategy,
+            config.range,
+            config.max_attempts,
+            config.cheating_host,
+            games,
+            &mut rng,
+        );
+        print!("\n{report}");
+    }
+}
+
+fn serve(address: &str, idle_timeout: Duration, config: Config) {
+    let listener = TcpListener::bind(address).unwrap_or_else(|err| {
+        eprintln!("Could not listen on {address}: {err}");
+        process::exit(1);
+    });
+    println!("Serving the guessing game on {address}");
+
+    if let Err(err) = server::serve(listener, Arc::new(config), idle_timeout) {
+        eprintln!("Server error: {err}");
+        process::exit(1);
+    }
+}
+
+fn load_recording(path: &Path) -> Recording {
+    Recording::load(path).unwrap_or_else(|err| {
+        eprintln!("Could not read the recording {}: {err}", path.display());
+        process::exit(1);
+    })
+}
+
+fn verify_replay(path: &Path) {
+    match load_recording(path).verify() {
+        Ok(None) => println!("{}: identical", path.display()),
+        Ok(Some(mismatch)) => {
+            println!("{}: {mismatch}", path.display());
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("Failed to replay {}: {err}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+fn scores_path(config: &Config) -> PathBuf {
+    config
+        .scores_path
+        .clone()
+        .or_else(scores::default_path)
+        .unwrap_or_else(|| PathBuf::from("scores.json"))
+}
+
+fn history_path(config: &Config) -> PathBuf {
+    config
+        .history_path
+        .clone()
+        .or_else(history::default_path)
+        .unwrap_or_else(|| PathBuf::from("history.jsonl"))
 }
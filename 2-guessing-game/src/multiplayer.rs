@@ -0,0 +1,213 @@
+//! Hot-seat play: several named players take turns guessing the same secret number.
+
+use crate::{Game, GuessOutcome};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub attempts: u32,
+    /// Distance of this player's closest guess so far.
+    pub best_distance: Option<u64>,
+    pub won: bool,
+}
+
+impl Player {
+    fn new(name: String) -> Player {
+        Player {
+            name,
+            attempts: 0,
+            best_distance: None,
+            won: false,
+        }
+    }
+}
+
+/// A shared `Game` plus whose turn it is.
+///
+/// The first player to find the number wins. With an attempts budget, each player gets their own
+/// budget and sits out once it is spent; the match is lost when everyone has spent theirs.
+#[derive(Debug)]
+pub struct Match {
+    game: Game,
+    players: Vec<Player>,
+    turn: usize,
+    max_attempts: Option<u32>,
+}
+
+impl Match {
+    /// `game` should not have an attempts budget of its own; `max_attempts` is per player.
+    ///
+    /// Panics if `names` is empty.
+    pub fn new(game: Game, names: Vec<String>, max_attempts: Option<u32>) -> Match {
+        assert!(!names.is_empty(), "a match needs at least one player");
+        Match {
+            game,
+            players: names.into_iter().map(Player::new).collect(),
+            turn: 0,
+            max_attempts,
+        }
+    }
+
+    /// The player whose turn it is.
+    pub fn current_player(&self) -> &Player {
+        &self.players[self.turn]
+    }
+
+    /// Plays `guess` for the current player and passes the turn on, unless the guess was out of
+    /// range and the player may try again.
+    pub fn submit_guess(&mut self, guess: i64) -> GuessOutcome {
+        let outcome = self.game.submit_guess(guess);
+        if outcome == GuessOutcome::OutOfRange {
+            return outcome;
+        }
+
+        let distance = self.game.distance(guess);
+        let player = &mut self.players[self.turn];
+        player.attempts += 1;
+        player.best_distance = Some(player.best_distance.map_or(distance, |d| d.min(distance)));
+        player.won = outcome == GuessOutcome::Correct;
+
+        if !self.game.is_over() {
+            match self.next_turn() {
+                Some(turn) => self.turn = turn,
+                None => self.game.give_up(),
+            }
+        }
+        outcome
+    }
+
+    /// The next player, in order, who still has attempts left.
+    fn next_turn(&self) -> Option<usize> {
+        (1..=self.players.len())
+            .map(|offset| (self.turn + offset) % self.players.len())
+            .find(|&index| self.has_attempts_left(&self.players[index]))
+    }
+
+    fn has_attempts_left(&self, player: &Player) -> bool {
+        self.max_attempts.is_none_or(|max| player.attempts < max)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.game.is_over()
+    }
+
+    pub fn winner(&self) -> Option<&Player> {
+        self.players.iter().find(|player| player.won)
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Players ranked by: the winner first, then closest guess, then fewest attempts.
+    pub fn leaderboard(&self) -> Vec<&Player> {
+        let mut players: Vec<&Player> = self.players.iter().collect();
+        players.sort_by_key(|player| {
+            (
+                !player.won,
+                player.best_distance.unwrap_or(u64::MAX),
+                player.attempts,
+            )
+        });
+        players
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameState, NumberRange};
+    use GuessOutcome::{Correct, OutOfRange, TooBig, TooSmall};
+
+    /// A match on 1..=100 whose secret number is 50.
+    fn new_match(names: &[&str], max_attempts: Option<u32>) -> Match {
+        let game = Game::with_secret(NumberRange::default(), 50);
+        let names = names.iter().map(|name| name.to_string()).collect();
+        Match::new(game, names, max_attempts)
+    }
+
+    /// Plays the guesses in turn and returns who made each one and its outcome.
+    fn play(round: &mut Match, guesses: &[i64]) -> Vec<(String, GuessOutcome)> {
+        guesses
+            .iter()
+            .map(|&guess| {
+                let player = round.current_player().name.clone();
+                (player, round.submit_guess(guess))
+            })
+            .collect()
+    }
+
+    fn names(players: &[&Player]) -> Vec<String> {
+        players.iter().map(|player| player.name.clone()).collect()
+    }
+
+    #[test]
+    fn turns_pass_in_order_except_after_a_guess_out_of_range() {
+        let mut round = new_match(&["ana", "bob", "cy"], None);
+        let turns = play(&mut round, &[10, 200, 90, 20, 60, 50]);
+        let expected = [
+            ("ana", TooSmall),
+            ("bob", OutOfRange),
+            ("bob", TooBig),
+            ("cy", TooSmall),
+            ("ana", TooBig),
+            ("bob", Correct),
+        ];
+        assert_eq!(
+            turns,
+            expected.map(|(name, outcome)| (name.to_string(), outcome))
+        );
+        assert!(round.is_over());
+        assert_eq!(
+            round.winner().map(|player| player.name.as_str()),
+            Some("bob")
+        );
+        let attempts: Vec<u32> = round.players.iter().map(|player| player.attempts).collect();
+        assert_eq!(attempts, [2, 2, 1]);
+    }
+
+    #[test]
+    fn the_match_is_lost_once_every_budget_is_spent() {
+        let mut round = new_match(&["ana", "bob", "cy"], Some(2));
+        play(&mut round, &[10, 20, 30, 40, 60]);
+        assert!(!round.is_over());
+        assert_eq!(round.current_player().name, "cy");
+
+        assert_eq!(round.submit_guess(70), TooBig);
+        assert!(round.is_over());
+        assert_eq!(round.game().state(), GameState::Lost);
+        assert_eq!(round.winner(), None);
+        assert!(round.players.iter().all(|player| player.attempts == 2));
+    }
+
+    #[test]
+    fn a_player_out_of_attempts_sits_out() {
+        let mut round = new_match(&["ana", "bob"], Some(2));
+        // bob has a guess left after ana has spent hers; the turn stays with him.
+        round.players[0].attempts = 1;
+        let turns = play(&mut round, &[10, 20, 30]);
+        let expected = [("ana", TooSmall), ("bob", TooSmall), ("bob", TooSmall)];
+        assert_eq!(
+            turns,
+            expected.map(|(name, outcome)| (name.to_string(), outcome))
+        );
+        assert_eq!(round.game().state(), GameState::Lost);
+    }
+
+    #[test]
+    fn the_leaderboard_ranks_the_winner_then_distance_then_attempts() {
+        let mut round = new_match(&["ana", "bob", "cy", "dee"], None);
+        play(&mut round, &[40, 45, 60, 30, 60]);
+        // ana and cy are both 10 away, but cy took fewer attempts; dee is farthest.
+        assert_eq!(names(&round.leaderboard()), ["bob", "cy", "ana", "dee"]);
+
+        play(&mut round, &[50]);
+        assert_eq!(names(&round.leaderboard()), ["bob", "cy", "ana", "dee"]);
+        assert!(round.leaderboard()[0].won);
+
+        // a player who never guessed is last.
+        let mut round = new_match(&["ana", "bob", "cy"], None);
+        play(&mut round, &[90, 50]);
+        assert_eq!(names(&round.leaderboard()), ["bob", "ana", "cy"]);
+    }
+}
@@ -0,0 +1,138 @@
+//! The `--protocol jsonl` mode, for driving the game from other programs.
+//!
+//! Every input line is a JSON object such as `{"guess": 42}` and every guess is answered with
+//! exactly one JSON line:
+//!
+//! ```text
+//! {"response":"too_small","attempts":1,"game_over":false}
+//! {"response":"correct","attempts":2,"game_over":true,"secret_number":57}
+//! ```
+//!
+//! For numbers, `response` is one of `too_small`, `too_big`, `correct`, `invalid_input` or
+//! `out_of_range`. Other puzzles take the guess as a string (`{"guess": "2024-02-29"}`) and answer
+//! with their own feedback: `earlier`, `later` or `correct` for dates, `{"exact":2,"partial":1}`
+//! for codes and one of `correct`, `present` or `absent` per letter for words; lines that are not
+//! a guess for them are answered with `invalid_input`.
+//!
+//! `attempts_left` is only present when the game has an attempts budget, and `secret_number`
+//! only once the game is over, so a wrong guess with `secret_number` set means the game was lost.
+//! With `--hints`, wrong guesses also carry a `hint` such as `{"band":"warm","trend":"closer"}`.
+//! With `--guess-time` or `--time-limit`, a guess that does not arrive in time is answered with
+//! `time_up` and the game is lost. The session ends when the game is over or the input is closed.
+
+use crate::{
+    hints::{Hint, Hints},
+    input::GuessError,
+    puzzle::{Feedback, NumberPuzzle, Puzzle},
+    timer, Game,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+/// Why a line was not answered with feedback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rejection {
+    InvalidInput,
+    OutOfRange,
+    /// No guess arrived within `--guess-time` or `--time-limit`; the game is lost.
+    TimeUp,
+}
+
+/// The feedback of the puzzle, or why there is none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Response<F> {
+    Feedback(F),
+    Rejected(Rejection),
+}
+
+/// A line read from the client; the guess is a number or a string.
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub guess: Value,
+}
+
+/// A line written back to the client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Reply<P: Puzzle = NumberPuzzle> {
+    pub response: Response<P::Feedback>,
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts_left: Option<u32>,
+    pub game_over: bool,
+    /// The solution, whatever the puzzle; the name dates from when there were only numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_number: Option<P::Guess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+impl<P: Puzzle> Reply<P> {
+    pub fn new(response: Response<P::Feedback>, game: &Game<P>) -> Reply<P> {
+        Reply {
+            response,
+            attempts: game.attempts(),
+            attempts_left: game.attempts_left(),
+            game_over: game.is_over(),
+            secret_number: game.solution(),
+            hint: None,
+        }
+    }
+}
+
+/// Answers a single input line, with a hint for wrong guesses when `hints` is given.
+pub fn respond<P: Puzzle>(game: &mut Game<P>, hints: Option<&mut Hints>, line: &str) -> Reply<P> {
+    let guess = match serde_json::from_str::<Request>(line).map(|request| request.guess) {
+        Ok(Value::String(guess)) => guess,
+        Ok(Value::Number(guess)) => guess.to_string(),
+        _ => return Reply::new(Response::Rejected(Rejection::InvalidInput), game),
+    };
+    let guess = match game.puzzle().parse_guess(&guess) {
+        Ok(guess) => guess,
+        Err(GuessError::OutOfRange { .. } | GuessError::Negative) => {
+            return Reply::new(Response::Rejected(Rejection::OutOfRange), game)
+        }
+        Err(_) => return Reply::new(Response::Rejected(Rejection::InvalidInput), game),
+    };
+
+    let feedback = game.submit_guess(guess);
+    let mut reply = Reply::new(Response::Feedback(feedback), game);
+    if let (true, Some(hints), Some(distance)) = (
+        feedback.counts() && !feedback.is_correct(),
+        hints,
+        game.puzzle().distance(&guess),
+    ) {
+        reply.hint = Some(hints.hint(distance));
+    }
+    reply
+}
+
+/// Plays `game` over `input` and `output` until it is over or the input is closed.
+pub fn run<P: Puzzle>(
+    game: &mut Game<P>,
+    mut hints: Option<Hints>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut line = String::new();
+
+    while !game.is_over() {
+        line.clear();
+        let reply = match input.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => respond(game, hints.as_mut(), &line),
+            Err(err) if timer::time_up(&err).is_some() => {
+                game.give_up();
+                Reply::new(Response::Rejected(Rejection::TimeUp), game)
+            }
+            Err(err) => return Err(err),
+        };
+        serde_json::to_writer(&mut output, &reply)?;
+        writeln!(output)?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,283 @@
+//! The family of puzzles a `Game` can be played with.
+//!
+//! A `Puzzle` knows its solution, parses guesses and answers them with its own kind of
+//! feedback; `Game` adds the attempts budget on top, so every variant shares the same loop, the
+//! text and JSON lines frontends, the server and recordings. Besides the secret number there
+//! are dates answered with "earlier" or "later", Mastermind-style color codes answered with exact
+//! and partial matches, and Wordle-style five-letter words answered letter by letter.
+
+use crate::{
+    config::{Config, Protocol},
+    hints::Hints,
+    i18n,
+    input::{self, GuessError},
+    Game, GameState, GuessOutcome, NumberRange,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+pub mod cheating;
+pub mod code;
+pub mod date;
+pub mod word;
+
+/// Something to be guessed.
+pub trait Puzzle {
+    type Guess: Copy + fmt::Debug + fmt::Display + Serialize;
+    type Feedback: Feedback;
+
+    /// What a person is told before the first guess, such as "Guess the number!".
+    fn intro(&self) -> String;
+
+    fn parse_guess(&self, input: &str) -> Result<Self::Guess, GuessError>;
+
+    fn check(&mut self, guess: &Self::Guess) -> Self::Feedback;
+
+    fn solution(&self) -> Self::Guess;
+
+    /// Called once the game is over. A puzzle that has not settled on its solution yet, like
+    /// the cheating host, must do it now and answer any later guess accordingly.
+    fn commit(&mut self) {}
+
+    /// What a person is told when the game is lost.
+    fn reveal(&self) -> String {
+        (i18n::messages().you_lose_answer)(&self.solution().to_string())
+    }
+
+    /// How far `guess` is from the solution, for puzzles that support hot/cold hints.
+    fn distance(&self, _guess: &Self::Guess) -> Option<u64> {
+        None
+    }
+}
+
+/// The answer to a guess; its `Display` is the message for a person.
+pub trait Feedback: Copy + fmt::Debug + fmt::Display + Serialize {
+    fn is_correct(&self) -> bool;
+
+    /// Whether the guess counts as an attempt.
+    fn counts(&self) -> bool {
+        true
+    }
+}
+
+/// A number in a range answered with `GuessOutcome`, whoever picks the number; what the
+/// solver's strategies can play.
+pub trait NumberGuessing: Puzzle<Guess = i64, Feedback = GuessOutcome> {
+    fn range(&self) -> NumberRange;
+}
+
+/// The original puzzle: a secret number in a range, answered with `Ordering`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberPuzzle {
+    pub(crate) range: NumberRange,
+    pub(crate) secret_number: i64,
+}
+
+impl Puzzle for NumberPuzzle {
+    type Guess = i64;
+    type Feedback = GuessOutcome;
+
+    fn intro(&self) -> String {
+        i18n::messages().guess_the_number.to_string()
+    }
+
+    fn parse_guess(&self, input: &str) -> Result<i64, GuessError> {
+        input::parse_guess(input, self.range)
+    }
+
+    fn check(&mut self, guess: &i64) -> GuessOutcome {
+        if !self.range.contains(*guess) {
+            return GuessOutcome::OutOfRange;
+        }
+        GuessOutcome::from(guess.cmp(&self.secret_number))
+    }
+
+    fn solution(&self) -> i64 {
+        self.secret_number
+    }
+
+    fn reveal(&self) -> String {
+        (i18n::messages().you_lose)(self.secret_number)
+    }
+
+    fn distance(&self, guess: &i64) -> Option<u64> {
+        Some(guess.abs_diff(self.secret_number))
+    }
+}
+
+impl NumberGuessing for NumberPuzzle {
+    fn range(&self) -> NumberRange {
+        self.range
+    }
+}
+
+impl Feedback for GuessOutcome {
+    fn is_correct(&self) -> bool {
+        *self == GuessOutcome::Correct
+    }
+
+    fn counts(&self) -> bool {
+        *self != GuessOutcome::OutOfRange
+    }
+}
+
+impl fmt::Display for GuessOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = i18n::messages();
+        f.write_str(match self {
+            GuessOutcome::TooSmall => messages.too_small,
+            GuessOutcome::TooBig => messages.too_big,
+            GuessOutcome::Correct => messages.you_win,
+            GuessOutcome::OutOfRange => messages.out_of_range,
+        })
+    }
+}
+
+/// Which puzzle `--puzzle` picks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PuzzleKind {
+    #[default]
+    Number,
+    Date,
+    Code,
+    Word,
+}
+
+impl PuzzleKind {
+    pub const ALL: [PuzzleKind; 4] = [
+        PuzzleKind::Number,
+        PuzzleKind::Date,
+        PuzzleKind::Code,
+        PuzzleKind::Word,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PuzzleKind::Number => "number",
+            PuzzleKind::Date => "date",
+            PuzzleKind::Code => "code",
+            PuzzleKind::Word => "word",
+        }
+    }
+
+    /// The attempts `--limited` allows: what a binary search needs for numbers and dates, what
+    /// Knuth's algorithm needs for codes, and the usual six for words.
+    pub fn limited_attempts(self, range: NumberRange) -> u32 {
+        match self {
+            PuzzleKind::Number => range.optimal_attempts(),
+            PuzzleKind::Date => date::DateRange::default().optimal_attempts(),
+            PuzzleKind::Code => 5,
+            PuzzleKind::Word => 6,
+        }
+    }
+}
+
+impl fmt::Display for PuzzleKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PuzzleKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<PuzzleKind, String> {
+        PuzzleKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| format!("unknown puzzle '{s}' (expected number, date, code or word)"))
+    }
+}
+
+/// A game of any puzzle, for code that only plays it and looks at the result.
+pub trait Session {
+    /// Plays over `input` and `output` until the game is over or the input is closed.
+    fn play(&mut self, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()>;
+
+    fn state(&self) -> GameState;
+
+    fn attempts(&self) -> u32;
+
+    /// The guesses that counted, as JSON: numbers for numbers and strings for the other puzzles.
+    fn guesses(&self) -> Vec<serde_json::Value>;
+}
+
+struct ProtocolSession<P: Puzzle> {
+    game: Game<P>,
+    hints: Option<Hints>,
+    protocol: Protocol,
+}
+
+impl<P: Puzzle> Session for ProtocolSession<P> {
+    fn play(&mut self, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+        self.protocol
+            .run(&mut self.game, self.hints.take(), input, output)
+    }
+
+    fn state(&self) -> GameState {
+        self.game.state()
+    }
+
+    fn attempts(&self) -> u32 {
+        self.game.attempts()
+    }
+
+    fn guesses(&self) -> Vec<serde_json::Value> {
+        self.game
+            .guesses()
+            .iter()
+            .filter_map(|guess| serde_json::to_value(guess).ok())
+            .collect()
+    }
+}
+
+/// Starts a game of the puzzle `config` asks for, with its attempts budget, hints and protocol,
+/// drawing the solution from `rng`.
+pub fn start(config: &Config, rng: &mut dyn RngCore) -> Box<dyn Session> {
+    fn session<P: Puzzle + 'static>(
+        game: Game<P>,
+        config: &Config,
+        hints: Option<Hints>,
+    ) -> Box<dyn Session> {
+        let game = match config.max_attempts {
+            Some(max_attempts) => game.limit_attempts(max_attempts),
+            None => game,
+        };
+        Box::new(ProtocolSession {
+            game,
+            hints,
+            protocol: config.protocol,
+        })
+    }
+
+    match config.puzzle {
+        PuzzleKind::Number if config.cheating_host => {
+            let host = cheating::CheatingHost::new(config.range);
+            session(Game::from_puzzle(host), config, None)
+        }
+        PuzzleKind::Number => {
+            let hints = config.hints.map(|bands| Hints::new(config.range, bands));
+            session(Game::from_rng(config.range, rng), config, hints)
+        }
+        PuzzleKind::Date => {
+            let range = date::DateRange::default();
+            let hints = config.hints.map(|bands| Hints::new(range.days(), bands));
+            let puzzle = date::DatePuzzle::from_rng(range, rng);
+            session(Game::from_puzzle(puzzle), config, hints)
+        }
+        PuzzleKind::Code => {
+            let puzzle = code::CodePuzzle::from_rng(rng);
+            session(Game::from_puzzle(puzzle), config, None)
+        }
+        PuzzleKind::Word => {
+            let puzzle = word::WordPuzzle::from_rng(rng);
+            session(Game::from_puzzle(puzzle), config, None)
+        }
+    }
+}
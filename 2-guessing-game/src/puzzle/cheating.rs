@@ -0,0 +1,96 @@
+//! The cheating host: a number puzzle that never picks its number.
+//!
+//! Instead the host keeps the interval of numbers that agree with every answer given so far,
+//! and answers each guess with whatever leaves that interval largest. Only when the interval is
+//! down to the guess itself does it admit the guess is correct, so every game is as long as the
+//! guesser's strategy allows in the worst case: binary search needs exactly
+//! `NumberRange::optimal_attempts` guesses and any other strategy needs at least as many.
+
+use super::{NumberGuessing, Puzzle};
+use crate::{
+    i18n,
+    input::{self, GuessError},
+    GuessOutcome, NumberRange,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheatingHost {
+    range: NumberRange,
+    /// The numbers that are still consistent with every answer.
+    low: i64,
+    high: i64,
+}
+
+impl CheatingHost {
+    pub fn new(range: NumberRange) -> CheatingHost {
+        CheatingHost {
+            range,
+            low: range.min(),
+            high: range.max(),
+        }
+    }
+
+    /// The interval the secret number could still be in, as far as the guesser can tell.
+    pub fn candidates(&self) -> (i64, i64) {
+        (self.low, self.high)
+    }
+}
+
+impl Puzzle for CheatingHost {
+    type Guess = i64;
+    type Feedback = GuessOutcome;
+
+    fn intro(&self) -> String {
+        i18n::messages().guess_the_number.to_string()
+    }
+
+    fn parse_guess(&self, input: &str) -> Result<i64, GuessError> {
+        input::parse_guess(input, self.range)
+    }
+
+    fn check(&mut self, guess: &i64) -> GuessOutcome {
+        let guess = *guess;
+        if !self.range.contains(guess) {
+            return GuessOutcome::OutOfRange;
+        }
+        if guess < self.low {
+            return GuessOutcome::TooSmall;
+        }
+        if guess > self.high {
+            return GuessOutcome::TooBig;
+        }
+
+        // how many numbers each answer would leave; ties keep the upper half.
+        let above = guess.abs_diff(self.high);
+        let below = guess.abs_diff(self.low);
+        if above == 0 && below == 0 {
+            GuessOutcome::Correct
+        } else if above >= below {
+            self.low = guess + 1;
+            GuessOutcome::TooSmall
+        } else {
+            self.high = guess - 1;
+            GuessOutcome::TooBig
+        }
+    }
+
+    /// The lowest number still consistent with the answers; it only becomes the secret number
+    /// once the game is over.
+    fn solution(&self) -> i64 {
+        self.low
+    }
+
+    fn reveal(&self) -> String {
+        (i18n::messages().you_lose)(self.low)
+    }
+
+    fn commit(&mut self) {
+        self.high = self.low;
+    }
+}
+
+impl NumberGuessing for CheatingHost {
+    fn range(&self) -> NumberRange {
+        self.range
+    }
+}
@@ -0,0 +1,204 @@
+//! Mastermind: guess a code of four colored pegs, answered with exact and partial matches.
+
+use super::{Feedback, Puzzle};
+use crate::{i18n, input::GuessError};
+use rand::{Rng, RngCore};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+pub const PEGS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+    Yellow,
+    Orange,
+    Purple,
+}
+
+impl Color {
+    pub const ALL: [Color; 6] = [
+        Color::Red,
+        Color::Green,
+        Color::Blue,
+        Color::Yellow,
+        Color::Orange,
+        Color::Purple,
+    ];
+
+    /// The letter a color is typed as.
+    pub fn letter(self) -> char {
+        match self {
+            Color::Red => 'r',
+            Color::Green => 'g',
+            Color::Blue => 'b',
+            Color::Yellow => 'y',
+            Color::Orange => 'o',
+            Color::Purple => 'p',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<Color> {
+        let letter = letter.to_ascii_lowercase();
+        Color::ALL
+            .into_iter()
+            .find(|color| color.letter() == letter)
+    }
+}
+
+/// Four pegs, typed as their letters, such as `rgby`. Colors may repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Code(pub [Color; PEGS]);
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|color| write!(f, "{}", color.letter()))
+    }
+}
+
+impl Serialize for Code {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// `exact` pegs have the right color in the right place and `partial` more have a right color
+/// in the wrong place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CodeFeedback {
+    pub exact: u8,
+    pub partial: u8,
+}
+
+impl Feedback for CodeFeedback {
+    fn is_correct(&self) -> bool {
+        usize::from(self.exact) == PEGS
+    }
+}
+
+impl fmt::Display for CodeFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = i18n::messages();
+        if self.is_correct() {
+            return f.write_str(messages.you_win);
+        }
+        f.write_str(&(messages.code_feedback)(self.exact, self.partial))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodePuzzle {
+    secret: Code,
+}
+
+impl CodePuzzle {
+    pub fn with_secret(secret: Code) -> CodePuzzle {
+        CodePuzzle { secret }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> CodePuzzle {
+        let pegs = [(); PEGS].map(|()| Color::ALL[rng.gen_range(0..Color::ALL.len())]);
+        CodePuzzle::with_secret(Code(pegs))
+    }
+}
+
+impl Puzzle for CodePuzzle {
+    type Guess = Code;
+    type Feedback = CodeFeedback;
+
+    fn intro(&self) -> String {
+        i18n::messages().guess_the_code.to_string()
+    }
+
+    fn parse_guess(&self, input: &str) -> Result<Code, GuessError> {
+        let letters: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+        if letters.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let invalid = || GuessError::Invalid((i18n::messages().invalid_code)(&letters));
+
+        let colors = letters
+            .chars()
+            .map(Color::from_letter)
+            .collect::<Option<Vec<Color>>>()
+            .ok_or_else(invalid)?;
+        let pegs = colors.try_into().map_err(|_| invalid())?;
+        Ok(Code(pegs))
+    }
+
+    fn check(&mut self, guess: &Code) -> CodeFeedback {
+        let exact = (0..PEGS)
+            .filter(|&i| guess.0[i] == self.secret.0[i])
+            .count();
+        // every color counts as often as it appears in both codes, wherever it is.
+        let common: usize = Color::ALL
+            .iter()
+            .map(|color| {
+                let count = |code: &Code| code.0.iter().filter(|peg| *peg == color).count();
+                count(guess).min(count(&self.secret))
+            })
+            .sum();
+        CodeFeedback {
+            exact: exact as u8,
+            partial: (common - exact) as u8,
+        }
+    }
+
+    fn solution(&self) -> Code {
+        self.secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(letters: &str) -> Code {
+        CodePuzzle::with_secret(Code([Color::Red; PEGS]))
+            .parse_guess(letters)
+            .unwrap()
+    }
+
+    #[test]
+    fn counts_exact_and_partial_pegs() {
+        // (secret, guess, exact, partial)
+        let cases = [
+            ("rgby", "rgby", 4, 0),
+            ("rgby", "ybgr", 0, 4),
+            ("rgby", "oopp", 0, 0),
+            ("rgby", "rgyb", 2, 2),
+            // a repeated color counts no more often than the other code has it.
+            ("rrgg", "grrr", 1, 2),
+            ("rgby", "rrrr", 1, 0),
+            ("rrrr", "rgby", 1, 0),
+            ("rrbb", "bbrr", 0, 4),
+            ("rrbg", "rbrr", 1, 2),
+            ("ppoo", "oppo", 2, 2),
+        ];
+        for (secret, guess, exact, partial) in cases {
+            let feedback = CodePuzzle::with_secret(code(secret)).check(&code(guess));
+            assert_eq!(
+                feedback,
+                CodeFeedback { exact, partial },
+                "{guess} against {secret}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_four_color_letters() {
+        let puzzle = CodePuzzle::with_secret(code("rgby"));
+        assert_eq!(code(" R g b y\n").to_string(), "rgby");
+        assert!(matches!(puzzle.parse_guess(" \n"), Err(GuessError::Empty)));
+        for input in ["rgb", "rgbyo", "rgbx", "1234"] {
+            assert!(
+                matches!(puzzle.parse_guess(input), Err(GuessError::Invalid(_))),
+                "{input:?}"
+            );
+        }
+    }
+}
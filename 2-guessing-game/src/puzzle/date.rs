@@ -0,0 +1,309 @@
+//! Guess a day of the calendar, answered with "earlier" or "later".
+
+use super::{Feedback, Puzzle};
+use crate::{i18n, input::GuessError, NumberRange};
+use rand::{Rng, RngCore};
+use serde::{Serialize, Serializer};
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// A day of the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// Days since 1970-01-01.
+    days: i64,
+}
+
+impl Date {
+    /// `None` unless `month` and `day` exist in `year`.
+    pub fn new(year: i64, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        // Howard Hinnant's days_from_civil, with years starting in March.
+        let year = if month <= 2 { year - 1 } else { year };
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Some(Date {
+            days: era * 146_097 + day_of_era - 719_468,
+        })
+    }
+
+    pub fn from_days(days: i64) -> Date {
+        Date { days }
+    }
+
+    pub fn days(self) -> i64 {
+        self.days
+    }
+
+    /// The year, month and day, the inverse of `Date::new`.
+    pub fn ymd(self) -> (i64, u32, u32) {
+        let days = self.days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+        let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parses `YYYY-MM-DD`.
+    fn from_str(s: &str) -> Result<Date, String> {
+        let invalid = || format!("'{s}' is not a YYYY-MM-DD date");
+        let mut parts = s.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let (year, month, day) = (next()?, next()?, next()?);
+        // `u32::from_str` also takes a `+`, which would pass for a digit.
+        let digits = |part: &str, len: usize| {
+            part.len() == len && part.bytes().all(|byte| byte.is_ascii_digit())
+        };
+        if !digits(year, 4) || !digits(month, 2) || !digits(day, 2) {
+            return Err(invalid());
+        }
+        let parse = |part: &str| part.parse::<u32>().map_err(|_| invalid());
+        Date::new(i64::from(parse(year)?), parse(month)?, parse(day)?).ok_or_else(invalid)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// The days a secret date is drawn from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    first: Date,
+    last: Date,
+}
+
+impl DateRange {
+    pub fn new(first: Date, last: Date) -> Result<DateRange, String> {
+        if first >= last {
+            return Err(format!("{first} must be before {last}"));
+        }
+        Ok(DateRange { first, last })
+    }
+
+    pub fn first(&self) -> Date {
+        self.first
+    }
+
+    pub fn last(&self) -> Date {
+        self.last
+    }
+
+    /// The range as day numbers, see `Date::days`.
+    pub fn days(&self) -> NumberRange {
+        NumberRange::new(self.first.days, self.last.days).expect("first is before last")
+    }
+
+    pub fn optimal_attempts(&self) -> u32 {
+        self.days().optimal_attempts()
+    }
+}
+
+impl Default for DateRange {
+    /// The 20th and 21st centuries.
+    fn default() -> DateRange {
+        DateRange {
+            first: Date::new(1900, 1, 1).expect("valid date"),
+            last: Date::new(2099, 12, 31).expect("valid date"),
+        }
+    }
+}
+
+/// Where the secret date is compared to a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateFeedback {
+    Earlier,
+    Later,
+    Correct,
+}
+
+impl Feedback for DateFeedback {
+    fn is_correct(&self) -> bool {
+        *self == DateFeedback::Correct
+    }
+}
+
+impl fmt::Display for DateFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = i18n::messages();
+        f.write_str(match self {
+            DateFeedback::Earlier => messages.earlier,
+            DateFeedback::Later => messages.later,
+            DateFeedback::Correct => messages.you_win,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatePuzzle {
+    range: DateRange,
+    secret: Date,
+}
+
+impl DatePuzzle {
+    /// Panics if `secret` is outside `range`.
+    pub fn with_secret(range: DateRange, secret: Date) -> DatePuzzle {
+        assert!(
+            range.first <= secret && secret <= range.last,
+            "secret date {secret} is outside {}..={}",
+            range.first,
+            range.last
+        );
+        DatePuzzle { range, secret }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(range: DateRange, rng: &mut R) -> DatePuzzle {
+        let days = rng.gen_range(range.first.days..=range.last.days);
+        DatePuzzle::with_secret(range, Date::from_days(days))
+    }
+
+    pub fn range(&self) -> DateRange {
+        self.range
+    }
+}
+
+impl Puzzle for DatePuzzle {
+    type Guess = Date;
+    type Feedback = DateFeedback;
+
+    fn intro(&self) -> String {
+        let (first, last) = (self.range.first.to_string(), self.range.last.to_string());
+        (i18n::messages().guess_the_date)(&first, &last)
+    }
+
+    fn parse_guess(&self, input: &str) -> Result<Date, GuessError> {
+        let messages = i18n::messages();
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        let date: Date = trimmed
+            .parse()
+            .map_err(|_| GuessError::Invalid((messages.not_a_date)(trimmed)))?;
+        if date < self.range.first || date > self.range.last {
+            let (first, last) = (self.range.first.to_string(), self.range.last.to_string());
+            return Err(GuessError::Invalid((messages.date_out_of_range)(
+                &first, &last,
+            )));
+        }
+        Ok(date)
+    }
+
+    fn check(&mut self, guess: &Date) -> DateFeedback {
+        match self.secret.cmp(guess) {
+            Ordering::Less => DateFeedback::Earlier,
+            Ordering::Greater => DateFeedback::Later,
+            Ordering::Equal => DateFeedback::Correct,
+        }
+    }
+
+    fn solution(&self) -> Date {
+        self.secret
+    }
+
+    fn distance(&self, guess: &Date) -> Option<u64> {
+        Some(guess.days.abs_diff(self.secret.days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_and_dates_convert_both_ways() {
+        let cases = [
+            ((1970, 1, 1), 0),
+            ((1969, 12, 31), -1),
+            ((1900, 1, 1), -25_567),
+            ((1900, 2, 28), -25_509),
+            // 1900 is not a leap year, so March follows February 28.
+            ((1900, 3, 1), -25_508),
+            ((2000, 2, 29), 11_016),
+            ((2000, 3, 1), 11_017),
+            ((2024, 2, 29), 19_782),
+            ((2024, 12, 31), 20_088),
+            ((2099, 12, 31), 47_481),
+        ];
+        for ((year, month, day), days) in cases {
+            let date = Date::new(year, month, day).unwrap();
+            assert_eq!(date.days(), days, "{year}-{month}-{day}");
+            assert_eq!(Date::from_days(days).ymd(), (year, month, day));
+        }
+    }
+
+    #[test]
+    fn every_day_of_the_range_round_trips() {
+        let range = DateRange::default().days();
+        for days in range.min()..=range.max() {
+            let date = Date::from_days(days);
+            let (year, month, day) = date.ymd();
+            assert_eq!(Date::new(year, month, day), Some(date));
+            assert_eq!(date.to_string().parse(), Ok(date));
+        }
+    }
+
+    #[test]
+    fn only_days_of_the_calendar_exist() {
+        assert_eq!(Date::new(1900, 2, 29), None);
+        assert!(Date::new(2000, 2, 29).is_some());
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert_eq!(Date::new(2024, 4, 31), None);
+        assert_eq!(Date::new(2024, 13, 1), None);
+        assert_eq!(Date::new(2024, 1, 0), None);
+    }
+
+    #[test]
+    fn parses_only_yyyy_mm_dd() {
+        assert_eq!("2024-02-29".parse(), Ok(Date::new(2024, 2, 29).unwrap()));
+        for input in [
+            "+999-01-01",
+            "2024-+1-01",
+            "2024-01-+1",
+            "999-01-01",
+            "2024-1-01",
+            "2024-01-1",
+            "2024-01",
+            "2024-02-30",
+            "2024/01/01",
+            "2024-01-01-",
+        ] {
+            assert!(input.parse::<Date>().is_err(), "{input:?}");
+        }
+    }
+}
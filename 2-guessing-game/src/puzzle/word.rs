@@ -0,0 +1,212 @@
+//! Wordle: guess a five-letter word, answered letter by letter.
+
+use super::{Feedback, Puzzle};
+use crate::{i18n, input::GuessError};
+use rand::{seq::SliceRandom, RngCore};
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+pub const LETTERS: usize = 5;
+
+/// The words a secret word is drawn from. Guesses may be any five letters.
+pub const WORDS: &[&str] = &[
+    "about", "above", "actor", "adult", "after", "again", "agree", "alarm", "album", "alive",
+    "allow", "alone", "angle", "apple", "argue", "arise", "award", "beach", "begin", "below",
+    "bench", "birth", "black", "blade", "blind", "block", "blood", "board", "brain", "bread",
+    "break", "brick", "brief", "bring", "broad", "brown", "brush", "build", "burst", "cabin",
+    "candy", "chain", "chair", "chalk", "charm", "chart", "cheap", "check", "chest", "chief",
+    "child", "civil", "claim", "class", "clean", "clear", "climb", "clock", "close", "cloud",
+    "coach", "coast", "count", "court", "cover", "crane", "crash", "cream", "crowd", "dance",
+    "delay", "depth", "dream", "dress", "drink", "drive", "early", "earth", "eight", "empty",
+    "enjoy", "enter", "equal", "event", "exact", "extra", "faith", "false", "fault", "field",
+    "fifty", "fight", "final", "flame", "floor", "focus", "force", "frame", "fresh", "front",
+    "fruit", "funny", "ghost", "giant", "given", "glass", "grace", "grade", "grain", "grand",
+    "grass", "great", "green", "group", "guard", "guess", "guest", "guide", "happy", "heart",
+    "heavy", "honey", "horse", "hotel", "house", "human", "humor", "ideal", "image", "index",
+    "inner", "input", "issue", "joint", "judge", "juice", "knife", "label", "large", "laser",
+    "later", "laugh", "layer", "learn", "lemon", "level", "light", "limit", "local", "logic",
+    "loose", "lucky", "lunch", "magic", "major", "march", "match", "metal", "model", "money",
+    "month", "motor", "mouse", "mouth", "music", "nerve", "never", "night", "noise", "north",
+    "novel", "nurse", "ocean", "offer", "order", "other", "owner", "paint", "panel", "paper",
+    "party", "peace", "phase", "phone", "piano", "piece", "pilot", "pitch", "place", "plain",
+    "plane", "plant", "plate", "point", "pound", "power", "press", "price", "pride", "prime",
+    "print", "prize", "proof", "proud", "queen", "quick", "quiet", "radio", "raise", "range",
+    "rapid", "ratio", "reach", "ready", "river", "robot", "rough", "round", "route", "royal",
+    "rural", "scale", "scene", "score", "sense", "serve", "seven", "shape", "share", "sharp",
+    "sheep", "shelf", "shell", "shift", "shirt", "shock", "short", "sight", "skill", "sleep",
+    "slice", "small", "smart", "smile", "smoke", "solid", "solve", "sound", "south", "space",
+    "spare", "speak", "speed", "spend", "spite", "split", "sport", "staff", "stage", "stand",
+    "start", "state", "steam", "steel", "stick", "stone", "store", "storm", "story", "sugar",
+    "sweet", "table", "taste", "teach", "thank", "theme", "thing", "think", "third", "throw",
+    "tiger", "title", "today", "topic", "total", "touch", "tower", "track", "trade", "train",
+    "treat", "trend", "trial", "truck", "trust", "truth", "uncle", "union", "unity", "upper",
+    "urban", "usual", "value", "video", "visit", "voice", "waste", "watch", "water", "wheel",
+    "while", "white", "whole", "woman", "world", "worry", "write", "wrong", "young", "youth",
+];
+
+/// Five lowercase ASCII letters, shown in capitals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Word(pub [u8; LETTERS]);
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|letter| write!(f, "{}", letter.to_ascii_uppercase() as char))
+    }
+}
+
+impl Serialize for Word {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// How one letter of a guess matches the secret word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LetterScore {
+    /// The right letter in the right place.
+    Correct,
+    /// A letter of the word, in another place.
+    Present,
+    Absent,
+}
+
+/// The score of every letter of a guess, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct WordFeedback(pub [LetterScore; LETTERS]);
+
+impl Feedback for WordFeedback {
+    fn is_correct(&self) -> bool {
+        self.0.iter().all(|score| *score == LetterScore::Correct)
+    }
+}
+
+impl fmt::Display for WordFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for score in self.0 {
+            f.write_str(match score {
+                LetterScore::Correct => "🟩",
+                LetterScore::Present => "🟨",
+                LetterScore::Absent => "⬛",
+            })?;
+        }
+        if self.is_correct() {
+            write!(f, " {}", i18n::messages().you_win)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordPuzzle {
+    secret: Word,
+}
+
+impl WordPuzzle {
+    /// Panics unless `secret` is five ASCII letters.
+    pub fn with_secret(secret: &str) -> WordPuzzle {
+        let secret = parse_word(secret)
+            .unwrap_or_else(|| panic!("'{secret}' is not a word of five letters"));
+        WordPuzzle { secret }
+    }
+
+    pub fn from_rng<R: RngCore + ?Sized>(rng: &mut R) -> WordPuzzle {
+        WordPuzzle::with_secret(WORDS.choose(rng).expect("the word list is not empty"))
+    }
+}
+
+fn parse_word(input: &str) -> Option<Word> {
+    let letters: [u8; LETTERS] = input.as_bytes().try_into().ok()?;
+    letters
+        .iter()
+        .all(u8::is_ascii_alphabetic)
+        .then(|| Word(letters.map(|letter| letter.to_ascii_lowercase())))
+}
+
+impl Puzzle for WordPuzzle {
+    type Guess = Word;
+    type Feedback = WordFeedback;
+
+    fn intro(&self) -> String {
+        i18n::messages().guess_the_word.to_string()
+    }
+
+    fn parse_guess(&self, input: &str) -> Result<Word, GuessError> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err(GuessError::Empty);
+        }
+        parse_word(trimmed)
+            .ok_or_else(|| GuessError::Invalid((i18n::messages().invalid_word)(trimmed)))
+    }
+
+    fn check(&mut self, guess: &Word) -> WordFeedback {
+        let mut scores = [LetterScore::Absent; LETTERS];
+        // letters of the secret word not matched yet; each one can make a single letter present.
+        let mut unmatched = Vec::with_capacity(LETTERS);
+        for ((score, guessed), secret) in scores.iter_mut().zip(guess.0).zip(self.secret.0) {
+            if guessed == secret {
+                *score = LetterScore::Correct;
+            } else {
+                unmatched.push(secret);
+            }
+        }
+        for (score, guessed) in scores.iter_mut().zip(guess.0) {
+            if *score == LetterScore::Correct {
+                continue;
+            }
+            if let Some(position) = unmatched.iter().position(|letter| *letter == guessed) {
+                unmatched.swap_remove(position);
+                *score = LetterScore::Present;
+            }
+        }
+        WordFeedback(scores)
+    }
+
+    fn solution(&self) -> Word {
+        self.secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use LetterScore::{Absent as A, Correct as C, Present as P};
+
+    #[test]
+    fn scores_every_letter() {
+        let cases = [
+            ("crane", "crane", [C, C, C, C, C]),
+            ("crane", "nacre", [P, P, P, P, C]),
+            ("crane", "moist", [A, A, A, A, A]),
+            // a letter guessed twice is present only as often as the word has it.
+            ("crane", "error", [P, C, A, A, A]),
+            ("alarm", "llama", [A, C, C, P, P]),
+            ("sheep", "eerie", [P, P, A, A, A]),
+            // a letter in the right place leaves none over for the same letter elsewhere.
+            ("spite", "tests", [A, P, P, C, A]),
+            ("eight", "geese", [P, P, A, A, A]),
+            ("sweet", "steer", [C, P, C, C, A]),
+        ];
+        for (secret, guess, scores) in cases {
+            let mut puzzle = WordPuzzle::with_secret(secret);
+            let feedback = puzzle.check(&parse_word(guess).unwrap());
+            assert_eq!(feedback, WordFeedback(scores), "{guess} against {secret}");
+        }
+    }
+
+    #[test]
+    fn parses_five_letters() {
+        let puzzle = WordPuzzle::with_secret("crane");
+        assert_eq!(puzzle.parse_guess(" Crane\n").unwrap().to_string(), "CRANE");
+        assert!(matches!(puzzle.parse_guess(""), Err(GuessError::Empty)));
+        for input in ["cran", "cranes", "cr4ne", "crâne"] {
+            assert!(
+                matches!(puzzle.parse_guess(input), Err(GuessError::Invalid(_))),
+                "{input:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Recording a session with `--record` and replaying it.
+//!
+//! A recording holds everything needed to play the session again: the seed, the range, the
+//! attempts budget, hints and protocol, plus every raw input line and every piece of output with
+//! the time it happened. `verify` feeds the recorded input to the current rules and checks that
+//! the output is identical, which makes recordings usable as regression tests; `play` prints the
+//! session again at any speed, for demos.
+
+use crate::{
+    config::{Config, Protocol},
+    hints::Bands,
+    i18n::{self, Lang},
+    input::{LineReader, LineSource},
+    puzzle::{PuzzleKind, Session},
+    NumberRange,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    fmt, fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Input,
+    Output,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    /// Milliseconds since the session started.
+    pub at_ms: u64,
+    pub kind: EventKind,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    /// Recordings made before there were other puzzles are of numbers.
+    #[serde(default)]
+    pub puzzle: PuzzleKind,
+    #[serde(default)]
+    pub cheating_host: bool,
+    pub seed: u64,
+    pub min: i64,
+    pub max: i64,
+    pub max_attempts: Option<u32>,
+    pub hints: Option<Bands>,
+    pub protocol: Protocol,
+    /// Recordings made before messages were translated are in English.
+    #[serde(default)]
+    pub lang: Lang,
+    pub events: Vec<Event>,
+}
+
+/// Where the output of a replayed session first differs from the recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// 1-based line of the output.
+    pub line: usize,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |line: &Option<String>| match line {
+            Some(line) => format!("{line:?}"),
+            None => "nothing".to_string(),
+        };
+        write!(
+            f,
+            "output line {} differs: recorded {}, now {}",
+            self.line,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> io::Result<Recording> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// A new game exactly like the recorded one.
+    pub fn session(&self) -> io::Result<Box<dyn Session>> {
+        let range = NumberRange::new(self.min, self.max)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let config = Config {
+            puzzle: self.puzzle,
+            cheating_host: self.cheating_host,
+            range,
+            max_attempts: self.max_attempts,
+            hints: self.hints,
+            protocol: self.protocol,
+            ..Config::default()
+        };
+        Ok(config.new_session(Some(self.seed)))
+    }
+
+    fn text_of(&self, kind: EventKind) -> String {
+        self.events
+            .iter()
+            .filter(|event| event.kind == kind)
+            .map(|event| event.text.as_str())
+            .collect()
+    }
+
+    /// Plays the recorded input against the current rules and compares the output; `None` means
+    /// it is identical. Switches the messages to the language of the recording.
+    pub fn verify(&self) -> io::Result<Option<Mismatch>> {
+        i18n::set_lang(self.lang);
+        let mut session = self.session()?;
+        let input = self.text_of(EventKind::Input);
+        let mut output = Vec::new();
+        session.play(&mut input.as_bytes(), &mut output)?;
+
+        let expected = self.text_of(EventKind::Output);
+        let actual = String::from_utf8_lossy(&output);
+        let (mut expected, mut actual) = (expected.lines(), actual.lines());
+        let mut line = 0;
+        loop {
+            line += 1;
+            match (expected.next(), actual.next()) {
+                (None, None) => return Ok(None),
+                (expected, actual) if expected == actual => {}
+                (expected, actual) => {
+                    return Ok(Some(Mismatch {
+                        line,
+                        expected: expected.map(str::to_string),
+                        actual: actual.map(str::to_string),
+                    }))
+                }
+            }
+        }
+    }
+
+    /// Prints the session again, waiting between events as long as the player did divided by
+    /// `speed`.
+    pub fn play(&self, speed: f64, mut output: impl Write) -> io::Result<()> {
+        let mut previous = 0;
+        for event in &self.events {
+            let wait = Duration::from_millis(event.at_ms.saturating_sub(previous));
+            thread::sleep(wait.div_f64(speed));
+            previous = event.at_ms;
+
+            match event.kind {
+                EventKind::Input => write!(output, "> {}", event.text)?,
+                EventKind::Output => write!(output, "{}", event.text)?,
+            }
+            output.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the events of a session as it is played.
+struct Recorder {
+    started: Instant,
+    events: RefCell<Vec<Event>>,
+}
+
+impl Recorder {
+    /// Output written in several pieces before the next input is kept as a single event.
+    fn push(&self, kind: EventKind, text: String) {
+        let mut events = self.events.borrow_mut();
+        match events.last_mut() {
+            Some(last) if kind == EventKind::Output && last.kind == EventKind::Output => {
+                last.text.push_str(&text);
+            }
+            _ => {
+                let at_ms = self.started.elapsed().as_millis() as u64;
+                events.push(Event { at_ms, kind, text });
+            }
+        }
+    }
+}
+
+/// The lines of `inner`, each recorded as it is read.
+struct RecordingInput<'a, R> {
+    inner: R,
+    recorder: &'a Recorder,
+}
+
+impl<R: BufRead> LineSource for RecordingInput<'_, R> {
+    fn next_line(&mut self) -> io::Result<Vec<u8>> {
+        let mut line = Vec::new();
+        if self.inner.read_until(b'\n', &mut line)? > 0 {
+            let text = String::from_utf8_lossy(&line).into_owned();
+            self.recorder.push(EventKind::Input, text);
+        }
+        Ok(line)
+    }
+}
+
+/// Writes to `inner`, recording everything written.
+struct RecordingOutput<'a, W> {
+    inner: W,
+    recorder: &'a Recorder,
+}
+
+impl<W: Write> Write for RecordingOutput<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        let text = String::from_utf8_lossy(&buf[..written]).into_owned();
+        self.recorder.push(EventKind::Output, text);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Plays `session` while recording it to `path`.
+///
+/// `seed` must be the seed `session` was started from, with the settings of `config`. The
+/// recording is saved even when playing fails, so that the failure can be replayed.
+pub fn record(
+    config: &Config,
+    seed: u64,
+    session: &mut dyn Session,
+    input: impl BufRead,
+    output: impl Write,
+    path: &Path,
+) -> io::Result<()> {
+    let recorder = Recorder {
+        started: Instant::now(),
+        events: RefCell::new(Vec::new()),
+    };
+    let mut input = LineReader::new(RecordingInput {
+        inner: input,
+        recorder: &recorder,
+    });
+    let mut output = RecordingOutput {
+        inner: output,
+        recorder: &recorder,
+    };
+    let result = session.play(&mut input, &mut output);
+
+    let recording = Recording {
+        puzzle: config.puzzle,
+        cheating_host: config.cheating_host,
+        seed,
+        min: config.range.min(),
+        max: config.range.max(),
+        max_attempts: config.max_attempts,
+        hints: config.hints,
+        protocol: config.protocol,
+        lang: config.lang,
+        events: recorder.events.into_inner(),
+    };
+    recording.save(path)?;
+    result
+}
@@ -0,0 +1,22 @@
+//! Points awarded for a won game.
+
+use crate::NumberRange;
+use std::time::Duration;
+
+/// Points for each guess a binary search of the range would need.
+const POINTS_PER_OPTIMAL_GUESS: u64 = 1000;
+/// Points lost for every second spent playing.
+const POINTS_PER_SECOND: u64 = 5;
+
+/// Scores a win from how many `attempts` it took and how long it lasted.
+///
+/// Finding the number in exactly as many guesses as a binary search needs is worth 1000 points
+/// per guess of that search, so bigger ranges are worth more. Fewer attempts are worth
+/// proportionally more and extra attempts proportionally less. Every second played then costs 5
+/// points, and the score never drops below zero.
+pub fn score(range: NumberRange, attempts: u32, elapsed: Duration) -> u64 {
+    let optimal = u64::from(range.optimal_attempts());
+    let base = POINTS_PER_OPTIMAL_GUESS * optimal * optimal / u64::from(attempts.max(1));
+
+    base.saturating_sub(POINTS_PER_SECOND * elapsed.as_secs())
+}
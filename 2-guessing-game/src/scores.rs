@@ -0,0 +1,96 @@
+//! The local high-score table.
+//!
+//! Scores live in a JSON file under the XDG data directory
+//! (`$XDG_DATA_HOME/guessing_game/scores.json`, falling back to `~/.local/share`). The file is
+//! always replaced atomically: the new table is written to a temporary file next to it and then
+//! renamed over it, so a crash midway never leaves a half-written table behind.
+
+use crate::{difficulty::Difficulty, NumberRange};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Reverse,
+    env,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// One won game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub player: String,
+    pub min: i64,
+    pub max: i64,
+    pub attempts: u32,
+    pub duration_ms: u64,
+    /// The `--seed` the game was played with, if any.
+    pub seed: Option<u64>,
+    pub score: u64,
+}
+
+impl ScoreEntry {
+    /// The preset the game was played on; `None` for a custom range.
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        NumberRange::new(self.min, self.max)
+            .ok()
+            .and_then(Difficulty::from_range)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScoreFile {
+    scores: Vec<ScoreEntry>,
+}
+
+/// Where the table is kept when `--scores` is not given.
+pub fn default_path() -> Option<PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+
+    Some(data_dir.join("guessing_game").join("scores.json"))
+}
+
+/// Reads every entry, best score first. A missing file is an empty table.
+pub fn load(path: &Path) -> io::Result<Vec<ScoreEntry>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let file: ScoreFile = serde_json::from_str(&contents)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let mut scores = file.scores;
+    scores.sort_by_key(|entry| Reverse(entry.score));
+    Ok(scores)
+}
+
+/// Adds `entry` to the table at `path`.
+pub fn record(path: &Path, entry: ScoreEntry) -> io::Result<()> {
+    let mut scores = load(path)?;
+    scores.push(entry);
+    save(path, scores)
+}
+
+/// Empties the table at `path`.
+pub fn reset(path: &Path) -> io::Result<()> {
+    save(path, Vec::new())
+}
+
+fn save(path: &Path, scores: Vec<ScoreEntry>) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(&ScoreFile { scores })?;
+
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut tmp = File::create(&tmp_path)?;
+    tmp.write_all(json.as_bytes())?;
+    tmp.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
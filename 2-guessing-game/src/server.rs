@@ -0,0 +1,127 @@
+//! The `serve` mode: an independent game for every TCP client.
+//!
+//! Each connection gets its own thread and its own `Game`, played over the socket with the same
+//! text or JSON-lines frontend as the terminal. A client that sends nothing for the idle timeout
+//! is told so and disconnected. Every finished session is logged to stdout.
+//!
+//! Try it with `cargo run -- serve --port 7878` and `nc localhost 7878`.
+
+use crate::{config::Config, i18n, GameState};
+use std::{
+    fmt,
+    io::{self, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How a session ended.
+#[derive(Debug)]
+pub enum SessionEnd {
+    Won,
+    Lost,
+    /// The client closed the connection before the game was over.
+    Disconnected,
+    TimedOut,
+    Failed(io::Error),
+}
+
+/// What gets logged for a finished session.
+#[derive(Debug)]
+pub struct SessionLog {
+    pub id: u64,
+    pub peer: Option<SocketAddr>,
+    pub end: SessionEnd,
+    pub attempts: u32,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for SessionLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let peer = match self.peer {
+            Some(peer) => peer.to_string(),
+            None => "unknown peer".to_string(),
+        };
+        let end = match &self.end {
+            SessionEnd::Won => "won".to_string(),
+            SessionEnd::Lost => "lost".to_string(),
+            SessionEnd::Disconnected => "disconnected".to_string(),
+            SessionEnd::TimedOut => "timed out".to_string(),
+            SessionEnd::Failed(err) => format!("failed ({err})"),
+        };
+        write!(
+            f,
+            "session {} from {peer}: {end} after {} attempts in {:.1}s",
+            self.id,
+            self.attempts,
+            self.elapsed.as_secs_f64()
+        )
+    }
+}
+
+/// Accepts clients on `listener` forever, playing one game per connection.
+///
+/// With a `--seed`, session `n` is seeded with `seed + n` so sessions differ but stay
+/// reproducible.
+pub fn serve(listener: TcpListener, config: Arc<Config>, idle_timeout: Duration) -> io::Result<()> {
+    for (id, stream) in (1..).zip(listener.incoming()) {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Failed to accept a connection: {err}");
+                continue;
+            }
+        };
+
+        let config = Arc::clone(&config);
+        thread::spawn(move || {
+            let log = handle(id, stream, &config, idle_timeout);
+            println!("{log}");
+        });
+    }
+
+    Ok(())
+}
+
+/// Plays one game with the client on `stream`.
+pub fn handle(id: u64, stream: TcpStream, config: &Config, idle_timeout: Duration) -> SessionLog {
+    let peer = stream.peer_addr().ok();
+    let started = Instant::now();
+    let mut session = config.new_session(config.seed.map(|seed| seed.wrapping_add(id)));
+
+    let result = stream
+        .set_read_timeout(Some(idle_timeout))
+        .and_then(|()| stream.try_clone())
+        .and_then(|reader| {
+            let mut input = BufReader::new(reader);
+            session.play(&mut input, &mut &stream)
+        });
+
+    let end = match result {
+        Ok(()) => match session.state() {
+            GameState::Won => SessionEnd::Won,
+            GameState::Lost => SessionEnd::Lost,
+            GameState::Playing => SessionEnd::Disconnected,
+        },
+        Err(err)
+            if matches!(
+                err.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            // the client may already be gone, so failing to say goodbye is fine.
+            let _ = writeln!(&stream, "{}", i18n::messages().idle_goodbye);
+            SessionEnd::TimedOut
+        }
+        Err(err) => SessionEnd::Failed(err),
+    };
+
+    SessionLog {
+        id,
+        peer,
+        end,
+        attempts: session.attempts(),
+        elapsed: started.elapsed(),
+    }
+}
@@ -0,0 +1,316 @@
+//! Automatic players, for benchmarking search strategies against the real game rules.
+//!
+//! A `Strategy` only ever sees the interval that is still consistent with the feedback so far,
+//! and `play` feeds its guesses to a `Game` exactly like a person would.
+
+use crate::{
+    puzzle::{cheating::CheatingHost, NumberGuessing},
+    Game, GameState, GuessOutcome, NumberRange,
+};
+use rand::{Rng, RngCore};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+/// Picks the next guess from the interval `low..=high` that still holds the secret number.
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    fn next_guess(&mut self, low: i64, high: i64, rng: &mut dyn RngCore) -> i64;
+}
+
+/// Always guesses the middle of the interval.
+pub struct BinarySearch;
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+
+    fn next_guess(&mut self, low: i64, high: i64, _rng: &mut dyn RngCore) -> i64 {
+        midpoint(low, high)
+    }
+}
+
+/// Guesses uniformly at random inside the interval.
+pub struct RandomProbe;
+
+impl Strategy for RandomProbe {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn next_guess(&mut self, low: i64, high: i64, rng: &mut dyn RngCore) -> i64 {
+        rng.gen_range(low..=high)
+    }
+}
+
+/// Guesses one third into the interval.
+pub struct Ternary;
+
+impl Strategy for Ternary {
+    fn name(&self) -> &'static str {
+        "ternary"
+    }
+
+    fn next_guess(&mut self, low: i64, high: i64, _rng: &mut dyn RngCore) -> i64 {
+        (low as i128 + (high as i128 - low as i128) / 3) as i64
+    }
+}
+
+/// Aims for the middle but misjudges it by up to a quarter of the interval, and now and then
+/// forgets what it learned and guesses anywhere in the game's range.
+pub struct HumanLike {
+    range: NumberRange,
+}
+
+impl HumanLike {
+    /// How often, out of 100 guesses, the interval is forgotten.
+    const FORGETFUL_PERCENT: u32 = 10;
+
+    pub fn new(range: NumberRange) -> HumanLike {
+        HumanLike { range }
+    }
+}
+
+impl Strategy for HumanLike {
+    fn name(&self) -> &'static str {
+        "human"
+    }
+
+    fn next_guess(&mut self, low: i64, high: i64, rng: &mut dyn RngCore) -> i64 {
+        if rng.gen_ratio(HumanLike::FORGETFUL_PERCENT, 100) {
+            return rng.gen_range(self.range.min()..=self.range.max());
+        }
+
+        let spread = (high as i128 - low as i128) / 4;
+        let guess = midpoint(low, high) as i128 + rng.gen_range(-spread..=spread);
+        guess.clamp(low as i128, high as i128) as i64
+    }
+}
+
+fn midpoint(low: i64, high: i64) -> i64 {
+    ((low as i128 + high as i128) / 2) as i64
+}
+
+/// The strategies that can be picked with `solve --strategy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyKind {
+    Binary,
+    Random,
+    Ternary,
+    Human,
+}
+
+impl StrategyKind {
+    pub const ALL: [StrategyKind; 4] = [
+        StrategyKind::Binary,
+        StrategyKind::Random,
+        StrategyKind::Ternary,
+        StrategyKind::Human,
+    ];
+
+    pub fn build(self, range: NumberRange) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::Binary => Box::new(BinarySearch),
+            StrategyKind::Random => Box::new(RandomProbe),
+            StrategyKind::Ternary => Box::new(Ternary),
+            StrategyKind::Human => Box::new(HumanLike::new(range)),
+        }
+    }
+}
+
+impl FromStr for StrategyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<StrategyKind, String> {
+        match s {
+            "binary" => Ok(StrategyKind::Binary),
+            "random" => Ok(StrategyKind::Random),
+            "ternary" => Ok(StrategyKind::Ternary),
+            "human" => Ok(StrategyKind::Human),
+            _ => Err(format!(
+                "unknown strategy '{s}' (expected binary, random, ternary or human)"
+            )),
+        }
+    }
+}
+
+/// Lets `strategy` play `game` until it is over.
+pub fn play<P: NumberGuessing>(
+    game: &mut Game<P>,
+    strategy: &mut dyn Strategy,
+    rng: &mut dyn RngCore,
+) {
+    let range = game.puzzle().range();
+    let (mut low, mut high) = (range.min(), range.max());
+
+    while !game.is_over() {
+        let guess = strategy.next_guess(low, high, rng);
+        match game.submit_guess(guess) {
+            GuessOutcome::TooSmall => low = low.max(guess + 1),
+            GuessOutcome::TooBig => high = high.min(guess - 1),
+            GuessOutcome::Correct | GuessOutcome::OutOfRange => {}
+        }
+    }
+}
+
+/// How a strategy did over many games.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub strategy: &'static str,
+    /// Number of games won with each attempt count.
+    pub wins_by_attempts: BTreeMap<u32, u32>,
+    pub losses: u32,
+    /// The binary search bound for the range, `ceil(log2(size + 1))`.
+    pub bound: u32,
+}
+
+impl Report {
+    pub fn games(&self) -> u32 {
+        self.wins_by_attempts.values().sum::<u32>() + self.losses
+    }
+
+    pub fn mean_attempts(&self) -> f64 {
+        let wins: u32 = self.wins_by_attempts.values().sum();
+        let attempts: u64 = self
+            .wins_by_attempts
+            .iter()
+            .map(|(&attempts, &count)| u64::from(attempts) * u64::from(count))
+            .sum();
+        attempts as f64 / f64::from(wins.max(1))
+    }
+
+    /// Games won in more attempts than the binary search bound.
+    pub fn over_bound(&self) -> u32 {
+        self.wins_by_attempts
+            .range(self.bound + 1..)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "strategy: {}", self.strategy)?;
+        writeln!(f, "games: {} (lost: {})", self.games(), self.losses)?;
+        writeln!(f, "mean attempts: {:.2}", self.mean_attempts())?;
+        writeln!(
+            f,
+            "binary search bound: {} (exceeded in {} games)",
+            self.bound,
+            self.over_bound()
+        )?;
+
+        let widest = self.wins_by_attempts.values().copied().max().unwrap_or(0);
+        for (attempts, &count) in &self.wins_by_attempts {
+            let bar = "#".repeat((count as usize * 40).div_ceil(widest.max(1) as usize));
+            writeln!(f, "{attempts:>4} {count:>7} {bar}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays `games` games on `range` with the strategy `kind`, drawing both the secret numbers and
+/// the strategy's choices from `rng`.
+///
+/// Against a cheating host every game is a worst case, so an optimal strategy wins all of them in
+/// exactly `bound` attempts.
+pub fn benchmark(
+    kind: StrategyKind,
+    range: NumberRange,
+    max_attempts: Option<u32>,
+    cheating_host: bool,
+    games: u32,
+    rng: &mut dyn RngCore,
+) -> Report {
+    let mut strategy = kind.build(range);
+    let mut report = Report {
+        strategy: strategy.name(),
+        bound: range.optimal_attempts(),
+        ..Report::default()
+    };
+
+    for _ in 0..games {
+        let (state, attempts) = if cheating_host {
+            let game = Game::from_puzzle(CheatingHost::new(range));
+            play_to_end(game, max_attempts, strategy.as_mut(), rng)
+        } else {
+            let game = Game::from_rng(range, rng);
+            play_to_end(game, max_attempts, strategy.as_mut(), rng)
+        };
+        match state {
+            GameState::Won => *report.wins_by_attempts.entry(attempts).or_insert(0) += 1,
+            _ => report.losses += 1,
+        }
+    }
+
+    report
+}
+
+fn play_to_end<P: NumberGuessing>(
+    mut game: Game<P>,
+    max_attempts: Option<u32>,
+    strategy: &mut dyn Strategy,
+    rng: &mut dyn RngCore,
+) -> (GameState, u32) {
+    if let Some(max_attempts) = max_attempts {
+        game = game.limit_attempts(max_attempts);
+    }
+    play(&mut game, strategy, rng);
+    (game.state(), game.attempts())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{difficulty::Difficulty, seeded_rng};
+
+    const GAMES: u32 = 500;
+
+    #[test]
+    fn binary_search_never_exceeds_the_bound() {
+        for difficulty in [Difficulty::Easy, Difficulty::Hard, Difficulty::Insane] {
+            let report = benchmark(
+                StrategyKind::Binary,
+                difficulty.range(),
+                None,
+                false,
+                GAMES,
+                &mut seeded_rng(7),
+            );
+            assert_eq!(report.losses, 0, "{}", difficulty.name());
+            assert_eq!(report.over_bound(), 0, "{}", difficulty.name());
+        }
+    }
+
+    #[test]
+    fn cheating_host_makes_binary_search_take_exactly_the_bound() {
+        for difficulty in Difficulty::ALL {
+            let report = benchmark(
+                StrategyKind::Binary,
+                difficulty.range(),
+                None,
+                true,
+                GAMES,
+                &mut seeded_rng(7),
+            );
+            let expected = BTreeMap::from([(report.bound, GAMES)]);
+            assert_eq!(report.wins_by_attempts, expected, "{}", difficulty.name());
+        }
+    }
+
+    #[test]
+    fn cheating_host_makes_every_strategy_take_at_least_the_bound() {
+        for kind in StrategyKind::ALL {
+            let report = benchmark(
+                kind,
+                Difficulty::Hard.range(),
+                None,
+                true,
+                GAMES,
+                &mut seeded_rng(7),
+            );
+            let fewest = report.wins_by_attempts.keys().next().copied();
+            assert!(fewest >= Some(report.bound), "{}", report.strategy);
+        }
+    }
+}
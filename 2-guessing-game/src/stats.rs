@@ -0,0 +1,475 @@
+//! The `stats` report over the game history.
+
+use crate::{
+    history::{HistoryEntry, Outcome},
+    i18n,
+    puzzle::{date::DateRange, PuzzleKind},
+};
+use std::{collections::BTreeMap, fmt};
+
+/// How many of the fastest wins are listed.
+const FASTEST: usize = 5;
+/// How many slices of the range the first-guess histogram has.
+const SLICES: usize = 10;
+
+/// Attempts of the games of one difficulty (or custom range, or other puzzle).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupStats {
+    pub label: String,
+    pub games: u32,
+    /// Attempts of every won game, fewest first.
+    pub won_attempts: Vec<u32>,
+}
+
+impl GroupStats {
+    pub fn wins(&self) -> u32 {
+        self.won_attempts.len() as u32
+    }
+
+    pub fn mean_attempts(&self) -> Option<f64> {
+        let total: u32 = self.won_attempts.iter().sum();
+        (!self.won_attempts.is_empty()).then(|| f64::from(total) / f64::from(self.wins()))
+    }
+
+    pub fn median_attempts(&self) -> Option<f64> {
+        let attempts = &self.won_attempts;
+        let middle = attempts.len() / 2;
+        match attempts.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(f64::from(attempts[middle])),
+            _ => Some(f64::from(attempts[middle - 1] + attempts[middle]) / 2.0),
+        }
+    }
+}
+
+/// How often the games of one group with one attempts budget were won.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitStats {
+    pub label: String,
+    pub max_attempts: u32,
+    pub games: u32,
+    pub wins: u32,
+}
+
+/// A player's won games against the binary search bound of their ranges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStats {
+    pub player: String,
+    pub wins: u32,
+    pub mean_attempts: f64,
+    pub mean_bound: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub games: u32,
+    pub won: u32,
+    pub lost: u32,
+    pub abandoned: u32,
+    pub groups: Vec<GroupStats>,
+    pub limits: Vec<LimitStats>,
+    /// The fastest wins, fastest first.
+    pub fastest: Vec<HistoryEntry>,
+    /// First guesses of number games by the slice of the range they fell in, lowest first.
+    pub first_guesses: [u32; SLICES],
+    /// Only players who won a game of numbers or dates, whose bound is a binary search.
+    pub players: Vec<PlayerStats>,
+}
+
+/// The difficulty of a number game, its range when custom, or the name of another puzzle.
+fn label(entry: &HistoryEntry) -> String {
+    let label = match (entry.difficulty(), entry.range()) {
+        (Some(difficulty), _) => difficulty.to_string(),
+        (None, Some(range)) => range.to_string(),
+        (None, None) => entry.puzzle.to_string(),
+    };
+    if entry.cheating_host {
+        return format!("{label}, {}", i18n::messages().cheating_host);
+    }
+    label
+}
+
+/// The binary search bound of the game, for puzzles that have one.
+fn bound(entry: &HistoryEntry) -> Option<u32> {
+    match entry.puzzle {
+        PuzzleKind::Number => entry.range().map(|range| range.optimal_attempts()),
+        PuzzleKind::Date => Some(DateRange::default().optimal_attempts()),
+        PuzzleKind::Code | PuzzleKind::Word => None,
+    }
+}
+
+impl Stats {
+    pub fn new(entries: &[HistoryEntry]) -> Stats {
+        let count = |outcome| entries.iter().filter(|e| e.outcome == outcome).count() as u32;
+
+        let mut groups = BTreeMap::<String, GroupStats>::new();
+        let mut limits = BTreeMap::<(String, u32), LimitStats>::new();
+        let mut players = BTreeMap::<&str, (u32, u32, u32)>::new();
+        let mut first_guesses = [0; SLICES];
+
+        for entry in entries {
+            let label = label(entry);
+            let won = entry.outcome == Outcome::Won;
+
+            let group = groups.entry(label.clone()).or_insert_with(|| GroupStats {
+                label: label.clone(),
+                games: 0,
+                won_attempts: Vec::new(),
+            });
+            group.games += 1;
+            if won {
+                group.won_attempts.push(entry.attempts);
+            }
+
+            // abandoned games say nothing about whether the budget was enough.
+            if let (Some(max_attempts), false) =
+                (entry.max_attempts, entry.outcome == Outcome::Abandoned)
+            {
+                let limit = limits
+                    .entry((label.clone(), max_attempts))
+                    .or_insert_with(|| LimitStats {
+                        label,
+                        max_attempts,
+                        games: 0,
+                        wins: 0,
+                    });
+                limit.games += 1;
+                limit.wins += u32::from(won);
+            }
+
+            if let (Some(range), Some(guess)) = (entry.range(), entry.first_guess()) {
+                let offset = (guess as i128 - range.min() as i128) as u128;
+                let slice = offset * SLICES as u128 / range.size();
+                first_guesses[(slice as usize).min(SLICES - 1)] += 1;
+            }
+
+            if let (true, Some(bound)) = (won, bound(entry)) {
+                let (wins, attempts, bounds) = players.entry(&entry.player).or_default();
+                *wins += 1;
+                *attempts += entry.attempts;
+                *bounds += bound;
+            }
+        }
+
+        let mut groups: Vec<GroupStats> = groups.into_values().collect();
+        for group in &mut groups {
+            group.won_attempts.sort_unstable();
+        }
+
+        let mut fastest: Vec<HistoryEntry> = entries
+            .iter()
+            .filter(|entry| entry.outcome == Outcome::Won)
+            .cloned()
+            .collect();
+        fastest.sort_by_key(|entry| entry.duration_ms);
+        fastest.truncate(FASTEST);
+
+        let players = players
+            .into_iter()
+            .map(|(player, (wins, attempts, bounds))| PlayerStats {
+                player: player.to_string(),
+                wins,
+                mean_attempts: f64::from(attempts) / f64::from(wins),
+                mean_bound: f64::from(bounds) / f64::from(wins),
+            })
+            .collect();
+
+        Stats {
+            games: entries.len() as u32,
+            won: count(Outcome::Won),
+            lost: count(Outcome::Lost),
+            abandoned: count(Outcome::Abandoned),
+            groups,
+            limits: limits.into_values().collect(),
+            fastest,
+            first_guesses,
+            players,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = i18n::messages();
+        let show =
+            |value: Option<f64>| value.map_or("-".to_string(), |value| format!("{value:.2}"));
+
+        writeln!(
+            f,
+            "{}",
+            (messages.stats_games)(self.games, self.won, self.lost, self.abandoned)
+        )?;
+
+        writeln!(f, "\n{}", messages.stats_attempts_heading)?;
+        for group in &self.groups {
+            let (mean, median) = (group.mean_attempts(), group.median_attempts());
+            writeln!(
+                f,
+                "{}",
+                (messages.stats_group)(
+                    &group.label,
+                    group.games,
+                    group.wins(),
+                    &show(mean),
+                    &show(median)
+                )
+            )?;
+        }
+
+        if !self.limits.is_empty() {
+            writeln!(f, "\n{}", messages.stats_limits_heading)?;
+        }
+        for limit in &self.limits {
+            let rate = f64::from(limit.wins) * 100.0 / f64::from(limit.games);
+            writeln!(
+                f,
+                "{}",
+                (messages.stats_limit)(
+                    &limit.label,
+                    limit.max_attempts,
+                    limit.wins,
+                    limit.games,
+                    rate
+                )
+            )?;
+        }
+
+        if !self.fastest.is_empty() {
+            writeln!(f, "\n{}", messages.stats_fastest_heading)?;
+        }
+        for (rank, entry) in self.fastest.iter().enumerate() {
+            writeln!(
+                f,
+                "{}",
+                (messages.stats_fastest)(
+                    rank + 1,
+                    &entry.player,
+                    &label(entry),
+                    entry.attempts,
+                    entry.duration_ms as f64 / 1000.0
+                )
+            )?;
+        }
+
+        let widest = self.first_guesses.iter().copied().max().unwrap_or(0);
+        if widest > 0 {
+            writeln!(f, "\n{}", messages.stats_first_guesses_heading)?;
+            for (slice, &count) in self.first_guesses.iter().enumerate() {
+                let slice = format!("{}-{}%", slice * 100 / SLICES, (slice + 1) * 100 / SLICES);
+                let bar = "#".repeat((count as usize * 40).div_ceil(widest as usize));
+                writeln!(f, "  {slice:>8} {count:>5} {bar}")?;
+            }
+        }
+
+        if !self.players.is_empty() {
+            writeln!(f, "\n{}", messages.stats_players_heading)?;
+        }
+        for player in &self.players {
+            writeln!(
+                f,
+                "{}",
+                (messages.stats_player)(
+                    &player.player,
+                    player.wins,
+                    player.mean_attempts,
+                    player.mean_bound,
+                    player.mean_attempts - player.mean_bound
+                )
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Lang;
+    use Outcome::{Abandoned, Lost, Won};
+
+    type Game = (&'static str, i64, i64, Outcome, u32, Option<u32>, i64, u64);
+
+    fn entry(
+        (player, min, max, outcome, attempts, max_attempts, first_guess, duration_ms): Game,
+    ) -> HistoryEntry {
+        HistoryEntry {
+            puzzle: PuzzleKind::Number,
+            cheating_host: false,
+            player: player.to_string(),
+            min,
+            max,
+            max_attempts,
+            outcome,
+            attempts,
+            duration_ms,
+            seed: None,
+            guesses: vec![first_guess.into()],
+        }
+    }
+
+    fn stats() -> Stats {
+        // (player, min, max, outcome, attempts, max attempts, first guess, duration in ms)
+        let games: [Game; 13] = [
+            ("ana", 1, 10, Won, 2, Some(4), 5, 3_000),
+            ("ana", 1, 10, Won, 4, Some(4), 1, 9_000),
+            ("bob", 1, 10, Won, 3, None, 10, 2_000),
+            ("bob", 1, 10, Lost, 4, Some(4), 6, 8_000),
+            ("cy", 1, 10, Abandoned, 1, Some(4), 3, 500),
+            ("dee", 1, 10, Won, 4, None, 2, 7_000),
+            ("ana", 1, 100, Won, 4, None, 50, 6_000),
+            ("ana", 1, 100, Won, 6, Some(7), 1, 4_000),
+            ("bob", 1, 100, Won, 7, None, 100, 10_000),
+            ("bob", 1, 100, Won, 11, None, 75, 12_000),
+            ("eve", 1, 50, Won, 3, None, 25, 1_000),
+            ("eve", 1, 50, Won, 8, None, 50, 11_000),
+            ("eve", 1, 50, Won, 5, None, 1, 13_000),
+        ];
+        let mut entries: Vec<HistoryEntry> = games.into_iter().map(entry).collect();
+        // words have no range and no bound, so they only count as games.
+        entries.push(HistoryEntry {
+            puzzle: PuzzleKind::Word,
+            min: 0,
+            max: 0,
+            guesses: vec!["crane".into()],
+            ..entry(("bob", 0, 0, Won, 3, None, 0, 1_500))
+        });
+        Stats::new(&entries)
+    }
+
+    #[test]
+    fn counts_the_outcomes() {
+        let stats = stats();
+        assert_eq!(
+            (stats.games, stats.won, stats.lost, stats.abandoned),
+            (14, 12, 1, 1)
+        );
+    }
+
+    #[test]
+    fn takes_the_mean_and_median_of_won_games() {
+        let groups: Vec<_> = stats()
+            .groups
+            .iter()
+            .map(|group| {
+                (
+                    group.label.clone(),
+                    group.games,
+                    group.won_attempts.clone(),
+                    group.mean_attempts(),
+                    group.median_attempts(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                // an odd count has a middle game, an even one the mean of the two in the middle.
+                (
+                    "1..=50".to_string(),
+                    3,
+                    vec![3, 5, 8],
+                    Some(16.0 / 3.0),
+                    Some(5.0)
+                ),
+                (
+                    "easy".to_string(),
+                    6,
+                    vec![2, 3, 4, 4],
+                    Some(3.25),
+                    Some(3.5)
+                ),
+                (
+                    "normal".to_string(),
+                    4,
+                    vec![4, 6, 7, 11],
+                    Some(7.0),
+                    Some(6.5)
+                ),
+                ("word".to_string(), 1, vec![3], Some(3.0), Some(3.0)),
+            ]
+        );
+
+        let lost = GroupStats {
+            label: "hard".to_string(),
+            games: 2,
+            won_attempts: Vec::new(),
+        };
+        assert_eq!((lost.mean_attempts(), lost.median_attempts()), (None, None));
+    }
+
+    #[test]
+    fn rates_wins_per_attempts_budget_without_abandoned_games() {
+        let limit = |label: &str, max_attempts, games, wins| LimitStats {
+            label: label.to_string(),
+            max_attempts,
+            games,
+            wins,
+        };
+        assert_eq!(
+            stats().limits,
+            [limit("easy", 4, 3, 2), limit("normal", 7, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn lists_the_fastest_wins() {
+        let fastest: Vec<_> = stats()
+            .fastest
+            .iter()
+            .map(|entry| (entry.player.clone(), entry.duration_ms))
+            .collect();
+        let expected = [
+            ("eve", 1_000),
+            ("bob", 1_500),
+            ("bob", 2_000),
+            ("ana", 3_000),
+            ("ana", 4_000),
+        ];
+        assert_eq!(
+            fastest,
+            expected.map(|(player, ms)| (player.to_string(), ms))
+        );
+    }
+
+    #[test]
+    fn slices_first_guesses_by_their_place_in_the_range() {
+        // the last number of a range falls in the last slice, not past it.
+        assert_eq!(stats().first_guesses, [3, 1, 1, 0, 3, 1, 0, 1, 0, 3]);
+    }
+
+    #[test]
+    fn compares_players_with_the_bound() {
+        let player = |player: &str, wins, mean_attempts, mean_bound| PlayerStats {
+            player: player.to_string(),
+            wins,
+            mean_attempts,
+            mean_bound,
+        };
+        assert_eq!(
+            stats().players,
+            [
+                player("ana", 4, 4.0, 5.5),
+                player("bob", 3, 7.0, 6.0),
+                player("dee", 1, 4.0, 4.0),
+                player("eve", 3, 16.0 / 3.0, 6.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn a_gap_of_zero_is_on_the_bound() {
+        let gaps = [
+            (Lang::En, 0.0, "on the bound"),
+            (Lang::En, 1e-9, "on the bound"),
+            (Lang::En, 1.5, "1.50 above"),
+            (Lang::En, -2.0 / 3.0, "0.67 below"),
+            (Lang::Pt, 0.0, "no limite"),
+            (Lang::Pt, 1.5, "1.50 acima"),
+            (Lang::Pt, -1.5, "1.50 abaixo"),
+        ];
+        for (lang, gap, expected) in gaps {
+            let line = (lang.messages().stats_player)("dee", 1, 4.0, 4.0 - gap, gap);
+            assert!(line.ends_with(expected), "{line:?}");
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! The plain line-based frontend: prompts and messages for a person.
+
+use crate::{
+    hints::Hints,
+    i18n,
+    input::{self, GuessError},
+    puzzle::{Feedback, Puzzle},
+    timer, Game, GameState,
+};
+use std::io::{self, BufRead, Write};
+
+/// Plays `game` over `input` and `output` until it is over or the input is closed.
+///
+/// Lines that are not a valid guess are explained and asked for again. Running out of time
+/// (see `timer`) loses the game; other I/O errors are returned.
+pub fn run<P: Puzzle>(
+    game: &mut Game<P>,
+    mut hints: Option<Hints>,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let messages = i18n::messages();
+
+    writeln!(output, "{}", game.puzzle().intro())?;
+    if let Some(max_attempts) = game.attempts_left() {
+        writeln!(output, "{}", (messages.you_have_attempts)(max_attempts))?;
+    }
+
+    while !game.is_over() {
+        writeln!(output, "{}", messages.please_input_your_guess)?;
+        output.flush()?;
+
+        let guess = match input::read_guess(&mut input, game.puzzle()) {
+            Ok(guess) => guess,
+            Err(GuessError::Eof) => {
+                writeln!(output, "{}", messages.no_more_input_goodbye)?;
+                break;
+            }
+            Err(GuessError::Io(err)) => match timer::time_up(&err) {
+                Some(time_up) => {
+                    writeln!(output, "{time_up}")?;
+                    game.give_up();
+                    writeln!(output, "{}", game.puzzle().reveal())?;
+                    break;
+                }
+                None => return Err(err),
+            },
+            Err(err) => {
+                writeln!(output, "{err}")?;
+                continue;
+            }
+        };
+        writeln!(output, "{}", (messages.you_guessed)(&guess))?;
+
+        let feedback = game.submit_guess(guess);
+        writeln!(output, "{feedback}")?;
+
+        if let (true, Some(hints), Some(distance)) = (
+            feedback.counts() && !feedback.is_correct(),
+            &mut hints,
+            game.puzzle().distance(&guess),
+        ) {
+            writeln!(output, "{}: {}", messages.hint_label, hints.hint(distance))?;
+        }
+
+        match (game.state(), game.attempts_left()) {
+            (GameState::Playing, Some(left)) => {
+                writeln!(output, "{}", (messages.attempts_left)(left))?
+            }
+            (GameState::Lost, _) => writeln!(output, "{}", game.puzzle().reveal())?,
+            _ => {}
+        }
+    }
+
+    output.flush()
+}
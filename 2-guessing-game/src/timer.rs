@@ -0,0 +1,112 @@
+//! Time limits: a countdown for every guess and one for the whole session.
+//!
+//! Reading a line from stdin blocks until the player presses enter, so `TimedInput` reads on a
+//! thread of its own and hands the lines over through a channel to the `LineReader` it is read
+//! through. Waiting on the channel can give up at a deadline, which makes a timeout visible even
+//! when nothing is typed at all.
+
+use crate::{i18n, input::LineSource};
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Which limit ran out. Reading from a `TimedInput` fails with an `io::ErrorKind::TimedOut`
+/// error holding this, see `time_up`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUp {
+    /// `--guess-time`
+    Guess,
+    /// `--time-limit`
+    Session,
+}
+
+impl fmt::Display for TimeUp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = i18n::messages();
+        f.write_str(match self {
+            TimeUp::Guess => messages.guess_time_up,
+            TimeUp::Session => messages.time_up,
+        })
+    }
+}
+
+impl Error for TimeUp {}
+
+/// The limit that ran out, if `err` comes from a `TimedInput` running out of time.
+pub fn time_up(err: &io::Error) -> Option<TimeUp> {
+    err.get_ref()?.downcast_ref::<TimeUp>().copied()
+}
+
+/// Lines of another reader, each of which has to arrive in time. Read it through a
+/// `LineReader`.
+pub struct TimedInput {
+    lines: Receiver<io::Result<String>>,
+    guess_time: Option<Duration>,
+    deadline: Option<Instant>,
+}
+
+impl TimedInput {
+    /// Reads `input` on a new thread. Every line must arrive within `guess_time` of being asked
+    /// for, and all of them within `time_limit` from now.
+    pub fn new(
+        mut input: impl BufRead + Send + 'static,
+        guess_time: Option<Duration>,
+        time_limit: Option<Duration>,
+    ) -> TimedInput {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut line = String::new();
+            let line = match input.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(_) => Ok(line),
+                Err(err) => Err(err),
+            };
+            let failed = line.is_err();
+            // the receiver is gone once the game is over; nobody is waiting for more lines.
+            if sender.send(line).is_err() || failed {
+                break;
+            }
+        });
+
+        TimedInput {
+            lines,
+            guess_time,
+            // a limit too far off for an `Instant` to hold never runs out.
+            deadline: time_limit.and_then(|limit| Instant::now().checked_add(limit)),
+        }
+    }
+}
+
+impl LineSource for TimedInput {
+    /// Waits for the next line, or for whichever limit runs out first.
+    fn next_line(&mut self) -> io::Result<Vec<u8>> {
+        let guess_deadline = self
+            .guess_time
+            .and_then(|time| Instant::now().checked_add(time));
+        let (deadline, limit) = match (guess_deadline, self.deadline) {
+            (Some(guess), Some(session)) if guess < session => (Some(guess), TimeUp::Guess),
+            (_, Some(session)) => (Some(session), TimeUp::Session),
+            (guess, None) => (guess, TimeUp::Guess),
+        };
+
+        let received = match deadline {
+            Some(deadline) => self
+                .lines
+                .recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => self
+                .lines
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(line) => line.map(String::into_bytes),
+            Err(RecvTimeoutError::Disconnected) => Ok(Vec::new()),
+            Err(RecvTimeoutError::Timeout) => Err(io::Error::new(io::ErrorKind::TimedOut, limit)),
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! The `--tui` frontend: a full-screen view of the same game loop.
+//!
+//! A number line shows the interval the secret number can still be in, shrinking with every
+//! "Too small!" or "Too big!", next to the guess history and an attempts meter.
+//! Keys: Enter guesses, Ctrl-N starts a new game, Esc or Ctrl-C quits.
+//!
+//! Every game with a guess in it is handed back when the player quits, so the caller can record
+//! it like a game played in text.
+
+use crate::{
+    config::Config,
+    hints::Hints,
+    i18n,
+    input::{self, GuessError},
+    Game, GameState, GuessOutcome,
+};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Gauge, List, ListItem, Paragraph},
+    DefaultTerminal, Frame,
+};
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+/// A game played in the terminal UI, won, lost or left unfinished.
+#[derive(Debug)]
+pub struct Round {
+    pub seed: Option<u64>,
+    pub game: Game,
+    /// Until the game was over, or until it was left.
+    pub elapsed: Duration,
+}
+
+/// Takes over the terminal until the player quits, and returns the games that were played.
+pub fn run(config: &Config) -> io::Result<Vec<Round>> {
+    let mut terminal = ratatui::try_init()?;
+    let result = App::new(config, config.seed, 1, Vec::new()).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+struct App<'a> {
+    config: &'a Config,
+    /// How many games were started, so seeded games differ from one another.
+    rounds: u64,
+    seed: Option<u64>,
+    game: Game,
+    started: Instant,
+    /// When the game was over, counted from `started`.
+    ended: Option<Duration>,
+    /// The games before this one.
+    played: Vec<Round>,
+    hints: Option<Hints>,
+    /// The interval the secret number can still be in.
+    low: i64,
+    high: i64,
+    history: Vec<(i64, GuessOutcome)>,
+    input: String,
+    message: String,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    fn new(config: &'a Config, seed: Option<u64>, rounds: u64, played: Vec<Round>) -> App<'a> {
+        let game = config.new_game(seed);
+        let range = game.range();
+        App {
+            config,
+            rounds,
+            seed,
+            hints: config.hints.map(|bands| Hints::new(range, bands)),
+            game,
+            started: Instant::now(),
+            ended: None,
+            played,
+            low: range.min(),
+            high: range.max(),
+            history: Vec::new(),
+            input: String::new(),
+            message: i18n::messages().guess_the_number.to_string(),
+            quit: false,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<Vec<Round>> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+        Ok(self.finish())
+    }
+
+    /// Every game played so far, this one included if it has a guess.
+    fn finish(self) -> Vec<Round> {
+        let mut played = self.played;
+        if self.game.attempts() > 0 {
+            played.push(Round {
+                seed: self.seed,
+                elapsed: self.ended.unwrap_or_else(|| self.started.elapsed()),
+                game: self.game,
+            });
+        }
+        played
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if ctrl => self.quit = true,
+            KeyCode::Char('n') if ctrl => self.new_game(),
+            KeyCode::Enter => self.submit(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) if !ctrl && !self.game.is_over() => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn new_game(&mut self) {
+        let seed = self.config.seed.map(|seed| seed.wrapping_add(self.rounds));
+        let (config, rounds) = (self.config, self.rounds);
+        let previous = std::mem::replace(self, App::new(config, seed, rounds + 1, Vec::new()));
+        self.played = previous.finish();
+    }
+
+    fn submit(&mut self) {
+        if self.game.is_over() {
+            return;
+        }
+        let messages = i18n::messages();
+        let line = std::mem::take(&mut self.input);
+        let guess = match input::parse_guess(&line, self.game.range()) {
+            Ok(guess) => guess,
+            Err(GuessError::Empty) => return,
+            Err(err) => {
+                self.message = err.to_string();
+                return;
+            }
+        };
+
+        let outcome = self.game.submit_guess(guess);
+        self.history.push((guess, outcome));
+        if self.game.is_over() {
+            self.ended = Some(self.started.elapsed());
+        }
+        self.message = match outcome {
+            GuessOutcome::TooSmall => {
+                self.low = self.low.max(guess + 1);
+                messages.too_small.to_string()
+            }
+            GuessOutcome::TooBig => {
+                self.high = self.high.min(guess - 1);
+                messages.too_big.to_string()
+            }
+            GuessOutcome::Correct => {
+                (self.low, self.high) = (guess, guess);
+                format!("{} {}", messages.you_win, messages.new_game_hint)
+            }
+            GuessOutcome::OutOfRange => messages.out_of_range.to_string(),
+        };
+
+        if let (GuessOutcome::TooSmall | GuessOutcome::TooBig, Some(hints)) =
+            (outcome, &mut self.hints)
+        {
+            let hint = hints.hint(self.game.distance(guess));
+            self.message = format!("{} ({hint})", self.message);
+        }
+        if self.game.state() == GameState::Lost {
+            let secret_number = self.game.secret_number().expect("the game is over");
+            self.message = format!(
+                "{}. {}",
+                (messages.you_lose)(secret_number),
+                messages.new_game_hint
+            );
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [title, number_line, middle, input, help] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(4),
+            Constraint::Min(5),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [history, status] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(middle);
+
+        let messages = i18n::messages();
+        frame.render_widget(
+            Line::from(messages.guess_the_number.bold()).centered(),
+            title,
+        );
+        self.draw_number_line(frame, number_line);
+        self.draw_history(frame, history);
+        self.draw_status(frame, status);
+
+        let prompt = Paragraph::new(self.input.as_str())
+            .block(Block::bordered().title(messages.please_input_your_guess));
+        frame.render_widget(prompt, input);
+        if !self.game.is_over() {
+            frame.set_cursor_position((input.x + 1 + self.input.len() as u16, input.y + 1));
+        }
+
+        frame.render_widget(Line::from(messages.keys_help.dark_gray()), help);
+    }
+
+    /// One cell per slice of the range, filled where the secret number can still be.
+    fn draw_number_line(&self, frame: &mut Frame, area: Rect) {
+        let messages = i18n::messages();
+        let block = Block::bordered().title(messages.number_line);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let range = self.game.range();
+        let width = inner.width.max(1) as i128;
+        let cells: String = (0..width)
+            .map(|cell| {
+                let from = range.min() as i128 + cell * range.size() as i128 / width;
+                let to = range.min() as i128 + (cell + 1) * range.size() as i128 / width - 1;
+                if to.max(from) >= self.low as i128 && from <= self.high as i128 {
+                    '█'
+                } else {
+                    '·'
+                }
+            })
+            .collect();
+
+        let remaining = (messages.still_possible)(self.low, self.high);
+        let lines = vec![
+            Line::from(cells.green()),
+            Line::from(vec![
+                Span::raw(range.min().to_string()),
+                Span::raw(format!("  {remaining}  ")).yellow(),
+                Span::raw(range.max().to_string()),
+            ])
+            .centered(),
+        ];
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    fn draw_history(&self, frame: &mut Frame, area: Rect) {
+        let messages = i18n::messages();
+        let items: Vec<ListItem> = self
+            .history
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(attempt, (guess, outcome))| {
+                let (text, color) = match outcome {
+                    GuessOutcome::TooSmall => (messages.too_small, Color::Blue),
+                    GuessOutcome::TooBig => (messages.too_big, Color::Red),
+                    GuessOutcome::Correct => (messages.you_win, Color::Green),
+                    GuessOutcome::OutOfRange => (messages.out_of_range, Color::DarkGray),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("#{:<3} {guess:>12}  ", attempt + 1)),
+                    Span::styled(text, Style::new().fg(color)),
+                ]))
+            })
+            .collect();
+        frame.render_widget(
+            List::new(items).block(Block::bordered().title(messages.history)),
+            area,
+        );
+    }
+
+    /// The attempts meter fills up against the budget, or against the binary search bound when
+    /// there is no budget.
+    fn draw_status(&self, frame: &mut Frame, area: Rect) {
+        let [meter, message] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(2)]).areas(area);
+
+        let messages = i18n::messages();
+        let attempts = self.game.attempts();
+        let (limit, label) = match self.config.max_attempts {
+            Some(max) => (max, (messages.attempts_of)(attempts, max)),
+            None => {
+                let bound = self.game.range().optimal_attempts();
+                (bound, (messages.attempts_bound)(attempts, bound))
+            }
+        };
+        let ratio = (f64::from(attempts) / f64::from(limit.max(1))).min(1.0);
+        let color = if ratio < 0.75 {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        let gauge = Gauge::default()
+            .block(Block::bordered().title(messages.attempts))
+            .gauge_style(Style::new().fg(color))
+            .ratio(ratio)
+            .label(label);
+        frame.render_widget(gauge, meter);
+
+        let text = Paragraph::new(self.message.as_str()).block(Block::bordered());
+        frame.render_widget(text, message);
+    }
+}
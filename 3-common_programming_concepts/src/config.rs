@@ -0,0 +1,197 @@
+//! Command line arguments of the binary.
+
+use crate::{
+    countdown::Countdown,
+    divisibility::{self, Format, Rule, Rules},
+    duration::Span,
+    int_types::{Int, IntType},
+    math,
+    overflow::Op,
+};
+use std::{fmt, str::FromStr};
+
+/// What the binary was asked to do.
+#[derive(Debug, Default, PartialEq)]
+pub enum Command {
+    /// No arguments: print the chapter notes, section by section.
+    #[default]
+    Chapters,
+    /// `types [value]`: every integer type with its width and bounds, and which of them can hold
+    /// `value`.
+    Types { value: Option<Int> },
+    /// `overflow <op> <lhs> <rhs> [type]`: every overflow strategy on one operation, on `i32`
+    /// unless a type is given.
+    Overflow {
+        op: Op,
+        lhs: Int,
+        rhs: Int,
+        kind: IntType,
+    },
+    /// `overflow edge-cases`: the strategies side by side on `overflow::edge_cases`.
+    OverflowEdgeCases,
+    /// `literal <literal>...`: the value and type of each number literal.
+    Literal { literals: Vec<String> },
+    /// `math factorial <n>`: `n!` as a `u64`, a `u128`, exactly, and recursively.
+    Factorial { n: u32 },
+    /// `math fibonacci <n>`: the `n`th Fibonacci number as a `u64`, a `u128` and exactly.
+    Fibonacci { n: u32 },
+    /// `math binomial <n> <k>`: `n` choose `k` as a `u64`, a `u128` and exactly.
+    Binomial { n: u64, k: u64 },
+    /// `math bench`: the iterative factorial against the recursive one.
+    MathBench,
+    /// `duration <span>`: a span such as `3h 15m` in seconds and back in human units.
+    Duration { span: Span },
+    /// `divisible <number> [divisor...]`: every divisor that divides the number, 4, 3 and 2
+    /// unless given.
+    Divisible { number: Int, divisors: Vec<u128> },
+    /// `fizzbuzz <start> <end> [divisor=label...] [--csv]`: the rules, `3=Fizz 5=Buzz` unless
+    /// given, on every number from `start` to `end`.
+    FizzBuzz {
+        start: Int,
+        end: Int,
+        rules: Rules,
+        format: Format,
+    },
+    /// `countdown [start] [--step <n>] [--interval <span>] [--message <text>] [--fake-clock]`:
+    /// a countdown that waits between the numbers, `Countdown::liftoff` for what is not given.
+    /// With `--fake-clock` it only pretends to wait.
+    Countdown {
+        countdown: Countdown,
+        fake_clock: bool,
+    },
+    /// `math safe-depth [stack bytes]`: how deep the recursive factorial can go on a stack of
+    /// that size, `math::DEFAULT_STACK` unless given.
+    SafeDepth { stack_size: usize },
+}
+
+impl Command {
+    /// Parses the arguments that follow the program name.
+    pub fn build(args: impl Iterator<Item = String>) -> Result<Command, String> {
+        let args: Vec<String> = args.collect();
+        let words: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        match words[..] {
+            [] => Ok(Command::Chapters),
+            ["types"] => Ok(Command::Types { value: None }),
+            ["types", value] => Ok(Command::Types {
+                value: Some(parse_value("types", value)?),
+            }),
+            ["overflow", "edge-cases"] => Ok(Command::OverflowEdgeCases),
+            ["overflow", op, lhs, rhs] | ["overflow", op, lhs, rhs, _] => Ok(Command::Overflow {
+                op: parse_value("the operation", op)?,
+                lhs: parse_value("the left operand", lhs)?,
+                rhs: parse_value("the right operand", rhs)?,
+                kind: match words.get(4) {
+                    Some(kind) => parse_value("the type", kind)?,
+                    None => IntType::I32,
+                },
+            }),
+            ["literal", ref literals @ ..] if !literals.is_empty() => Ok(Command::Literal {
+                literals: literals.iter().map(|literal| literal.to_string()).collect(),
+            }),
+            ["duration", span] => Ok(Command::Duration {
+                span: parse_value("duration", span)?,
+            }),
+            ["divisible", number, ref divisors @ ..] => Ok(Command::Divisible {
+                number: parse_value("the number", number)?,
+                divisors: if divisors.is_empty() {
+                    divisibility::IF_EXPRESSION_DIVISORS.to_vec()
+                } else {
+                    divisors
+                        .iter()
+                        .map(|divisor| parse_value("a divisor", divisor))
+                        .collect::<Result<_, _>>()?
+                },
+            }),
+            ["fizzbuzz", start, end, ref options @ ..] => fizzbuzz(start, end, options),
+            ["countdown", ref options @ ..] => countdown(options),
+            ["math", "factorial", n] => Ok(Command::Factorial {
+                n: parse_value("n", n)?,
+            }),
+            ["math", "fibonacci", n] => Ok(Command::Fibonacci {
+                n: parse_value("n", n)?,
+            }),
+            ["math", "binomial", n, k] => Ok(Command::Binomial {
+                n: parse_value("n", n)?,
+                k: parse_value("k", k)?,
+            }),
+            ["math", "bench"] => Ok(Command::MathBench),
+            ["math", "safe-depth"] => Ok(Command::SafeDepth {
+                stack_size: math::DEFAULT_STACK,
+            }),
+            ["math", "safe-depth", stack_size] => Ok(Command::SafeDepth {
+                stack_size: parse_value("the stack size", stack_size)?,
+            }),
+            _ => Err(format!("unknown command '{}'", words.join(" "))),
+        }
+    }
+}
+
+fn fizzbuzz(start: &str, end: &str, options: &[&str]) -> Result<Command, String> {
+    let start = parse_value("the start", start)?;
+    let end = parse_value("the end", end)?;
+    if start > end {
+        return Err(format!("the range {start} to {end} is empty"));
+    }
+
+    let mut format = Format::Text;
+    let mut rules = Vec::new();
+    for option in options {
+        match *option {
+            "--csv" => format = Format::Csv,
+            rule => rules.push(rule.parse::<Rule>()?),
+        }
+    }
+    let rules = if rules.is_empty() {
+        Rules::fizzbuzz()
+    } else {
+        Rules::new(rules)
+    };
+
+    Ok(Command::FizzBuzz {
+        start,
+        end,
+        rules,
+        format,
+    })
+}
+
+fn countdown(options: &[&str]) -> Result<Command, String> {
+    let mut countdown = Countdown::liftoff();
+    let mut fake_clock = false;
+    let mut options = options.iter();
+    while let Some(&option) = options.next() {
+        let mut value = |flag: &str| {
+            options
+                .next()
+                .copied()
+                .ok_or_else(|| format!("{flag} needs a value"))
+        };
+        match option {
+            "--step" => countdown.step = parse_value("--step", value("--step")?)?,
+            "--interval" => countdown.interval = parse_value("--interval", value("--interval")?)?,
+            "--message" => countdown.message = value("--message")?.to_string(),
+            "--fake-clock" => fake_clock = true,
+            start if !start.starts_with("--") => countdown.start = parse_value("the start", start)?,
+            flag => return Err(format!("unknown flag '{flag}'")),
+        }
+    }
+    if countdown.step == 0 {
+        return Err("--step must be at least 1".to_string());
+    }
+
+    Ok(Command::Countdown {
+        countdown,
+        fake_clock,
+    })
+}
+
+fn parse_value<T>(what: &str, value: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|err| format!("invalid value '{value}' for {what}: {err}"))
+}
@@ -0,0 +1,269 @@
+//! The countdown of `loops_while` and `for_loop` as a real timer: it waits between the numbers,
+//! can be paused and resumed, and stops cleanly when aborted.
+//!
+//! The waiting goes through a `Clock`, which is also where pauses and aborts come from.
+//! `SystemClock` sleeps for real and takes its events from a channel; `FakeClock` only pretends
+//! to, following a script of events, so a countdown can be checked without waiting for it:
+//!
+//! ```
+//! use common_programming_concepts::countdown::{Clock, Countdown, Event, FakeClock, Outcome};
+//! use common_programming_concepts::duration;
+//! use std::time::Duration;
+//!
+//! let countdown = Countdown {
+//!     start: 3,
+//!     step: 1,
+//!     interval: duration::seconds(1),
+//!     message: "LIFTOFF!!!".to_string(),
+//! };
+//! // paused for 10 seconds in the middle of the first second.
+//! let mut clock = FakeClock::new(vec![
+//!     (Duration::from_millis(500), Event::Toggle),
+//!     (Duration::from_millis(10_500), Event::Toggle),
+//! ]);
+//! let mut out = Vec::new();
+//!
+//! let outcome = countdown.run(&mut clock, &mut out).unwrap();
+//! assert_eq!(outcome, Outcome::Liftoff);
+//! assert_eq!(clock.now(), Duration::from_secs(13));
+//! assert_eq!(
+//!     String::from_utf8(out).unwrap(),
+//!     "3\npaused\nresumed\n2\n1\nLIFTOFF!!!\n"
+//! );
+//! ```
+
+use crate::duration::{self, Span};
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+/// What can interrupt the wait between two numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Pauses a running countdown, or resumes a paused one.
+    Toggle,
+    /// Stops the countdown, as Ctrl-C does.
+    Abort,
+}
+
+/// Time, and the events that arrive while waiting for it to pass.
+pub trait Clock {
+    /// The time since the clock started.
+    fn now(&self) -> Duration;
+
+    /// Waits until `timeout` has passed, or with `None` for as long as it takes, and returns the
+    /// event that ended the wait early. Without a timeout, `None` means that no event can come
+    /// anymore.
+    fn wait(&mut self, timeout: Option<Duration>) -> Option<Event>;
+}
+
+/// The real time, with the events sent through a channel.
+pub struct SystemClock {
+    started: Instant,
+    events: Receiver<Event>,
+}
+
+impl SystemClock {
+    pub fn new(events: Receiver<Event>) -> SystemClock {
+        SystemClock {
+            started: Instant::now(),
+            events,
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> Option<Event> {
+        match timeout {
+            Some(timeout) => match self.events.recv_timeout(timeout) {
+                Ok(event) => Some(event),
+                Err(RecvTimeoutError::Timeout) => None,
+                // nothing can interrupt the wait anymore, but it still has to last.
+                Err(RecvTimeoutError::Disconnected) => {
+                    std::thread::sleep(timeout);
+                    None
+                }
+            },
+            None => self.events.recv().ok(),
+        }
+    }
+}
+
+/// A clock that jumps ahead instead of sleeping. `script` lists the events with the time they
+/// happen at, in order.
+#[derive(Debug, Default)]
+pub struct FakeClock {
+    now: Duration,
+    script: VecDeque<(Duration, Event)>,
+}
+
+impl FakeClock {
+    pub fn new(script: Vec<(Duration, Event)>) -> FakeClock {
+        FakeClock {
+            now: Duration::ZERO,
+            script: script.into(),
+        }
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> Option<Event> {
+        // a wait longer than a `Duration` can count ends at its largest value.
+        let deadline = timeout.map(|timeout| self.now.saturating_add(timeout));
+        match self.script.front() {
+            Some(&(at, event)) if deadline.is_none_or(|deadline| at < deadline) => {
+                self.script.pop_front();
+                self.now = self.now.max(at);
+                Some(event)
+            }
+            _ => {
+                if let Some(deadline) = deadline {
+                    self.now = deadline;
+                }
+                None
+            }
+        }
+    }
+}
+
+/// How a countdown ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// It got through every number and printed its message.
+    Liftoff,
+    /// It was aborted while showing `at`.
+    Aborted { at: u64 },
+}
+
+/// Counts down from `start` by `step`, one number every `interval`, while the numbers are above
+/// zero, then prints `message`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Countdown {
+    pub start: u64,
+    /// Must not be zero.
+    pub step: u64,
+    pub interval: Span,
+    pub message: String,
+}
+
+impl Countdown {
+    /// `loops_while` and `for_loop`: 3, 2, 1, `LIFTOFF!!!`, a second apart.
+    pub fn liftoff() -> Countdown {
+        Countdown {
+            start: 3,
+            step: 1,
+            interval: duration::seconds(1),
+            message: "LIFTOFF!!!".to_string(),
+        }
+    }
+
+    /// The numbers shown, from `start` down.
+    pub fn numbers(&self) -> impl Iterator<Item = u64> {
+        (1..=self.start).rev().step_by(self.step as usize)
+    }
+
+    /// Runs the countdown on `clock`, writing the numbers, the pauses and the message to `out`.
+    pub fn run(&self, clock: &mut impl Clock, out: &mut impl Write) -> io::Result<Outcome> {
+        for number in self.numbers() {
+            writeln!(out, "{number}")?;
+            out.flush()?;
+            if !self.wait(clock, out)? {
+                return Ok(Outcome::Aborted { at: number });
+            }
+        }
+        writeln!(out, "{}", self.message)?;
+        Ok(Outcome::Liftoff)
+    }
+
+    /// Waits one interval, not counting the time spent paused. False when aborted.
+    fn wait(&self, clock: &mut impl Clock, out: &mut impl Write) -> io::Result<bool> {
+        let mut remaining = self.interval.to_std();
+        loop {
+            let started = clock.now();
+            match clock.wait(Some(remaining)) {
+                None => return Ok(true),
+                Some(Event::Abort) => return Ok(false),
+                Some(Event::Toggle) => {
+                    remaining = remaining.saturating_sub(clock.now() - started);
+                    writeln!(out, "paused")?;
+                    out.flush()?;
+                    match clock.wait(None) {
+                        Some(Event::Abort) => return Ok(false),
+                        // with no event left to come, nothing could resume it later, so it
+                        // resumes now.
+                        Some(Event::Toggle) | None => writeln!(out, "resumed")?,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(countdown: &Countdown, script: Vec<(Duration, Event)>) -> (Outcome, String, Duration) {
+        let mut clock = FakeClock::new(script);
+        let mut out = Vec::new();
+        let outcome = countdown.run(&mut clock, &mut out).unwrap();
+        (outcome, String::from_utf8(out).unwrap(), clock.now())
+    }
+
+    #[test]
+    fn counts_down_by_the_step() {
+        let countdown = Countdown {
+            start: 10,
+            step: 3,
+            interval: duration::minutes(1),
+            message: "Go!".to_string(),
+        };
+        let (outcome, out, now) = run(&countdown, Vec::new());
+        assert_eq!(outcome, Outcome::Liftoff);
+        assert_eq!(out, "10\n7\n4\n1\nGo!\n");
+        assert_eq!(now, Duration::from_secs(4 * 60));
+    }
+
+    #[test]
+    fn aborts_while_waiting_or_paused() {
+        let countdown = Countdown::liftoff();
+        let (outcome, out, now) = run(
+            &countdown,
+            vec![(Duration::from_millis(1500), Event::Abort)],
+        );
+        assert_eq!(outcome, Outcome::Aborted { at: 2 });
+        assert_eq!(out, "3\n2\n");
+        assert_eq!(now, Duration::from_millis(1500));
+
+        let script = vec![
+            (Duration::from_millis(200), Event::Toggle),
+            (Duration::from_secs(60), Event::Abort),
+        ];
+        let (outcome, out, _) = run(&countdown, script);
+        assert_eq!(outcome, Outcome::Aborted { at: 3 });
+        assert_eq!(out, "3\npaused\n");
+    }
+
+    #[test]
+    fn the_longest_interval_does_not_overflow_the_clock() {
+        let countdown = Countdown {
+            interval: duration::seconds(u64::MAX),
+            ..Countdown::liftoff()
+        };
+        let (outcome, out, now) = run(&countdown, Vec::new());
+        assert_eq!(outcome, Outcome::Liftoff);
+        assert_eq!(out, "3\n2\n1\nLIFTOFF!!!\n");
+        assert_eq!(now, Duration::MAX);
+    }
+}
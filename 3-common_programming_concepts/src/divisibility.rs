@@ -0,0 +1,244 @@
+//! The `else if` chain of `if_expression`, generalized: which of a list of divisors divide a
+//! number. The chain stops at the first true condition, so 12 is only "divisible by 4"; here every
+//! divisor that matches is reported.
+//!
+//! `Rules` build FizzBuzz on top of it: each rule gives a divisor a label, and a number gets the
+//! labels of all the rules that match it, in order, so 15 is `FizzBuzz`.
+
+use crate::int_types::Int;
+use std::{fmt, str::FromStr};
+
+/// The divisors `if_expression` tries.
+pub const IF_EXPRESSION_DIVISORS: [u128; 3] = [4, 3, 2];
+
+/// Every one of `divisors` that divides `number`, in the order given.
+pub fn divisors_of(number: Int, divisors: &[u128]) -> Vec<u128> {
+    divisors
+        .iter()
+        .copied()
+        .filter(|&divisor| number.is_multiple_of(divisor))
+        .collect()
+}
+
+/// The numbers from `start` to `end`, both included.
+pub fn range(start: Int, end: Int) -> impl Iterator<Item = Int> {
+    std::iter::successors(Some(start), |number| number.checked_add_unsigned(1))
+        .take_while(move |number| *number <= end)
+}
+
+/// A label for the multiples of a divisor, written `3=Fizz`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub divisor: u128,
+    pub label: String,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.divisor, self.label)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rule, String> {
+        let (divisor, label) = s
+            .split_once('=')
+            .ok_or_else(|| format!("'{s}' is not a rule (expected divisor=label, like 3=Fizz)"))?;
+        let divisor = divisor
+            .trim()
+            .parse()
+            .map_err(|err| format!("invalid divisor '{divisor}': {err}"))?;
+        if label.is_empty() {
+            return Err(format!("the rule '{s}' has no label"));
+        }
+        Ok(Rule {
+            divisor,
+            label: label.to_string(),
+        })
+    }
+}
+
+/// Rules applied together, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules(Vec<Rule>);
+
+impl Rules {
+    pub fn new(rules: Vec<Rule>) -> Rules {
+        Rules(rules)
+    }
+
+    /// `3=Fizz 5=Buzz`.
+    pub fn fizzbuzz() -> Rules {
+        Rules(vec![
+            Rule {
+                divisor: 3,
+                label: "Fizz".to_string(),
+            },
+            Rule {
+                divisor: 5,
+                label: "Buzz".to_string(),
+            },
+        ])
+    }
+
+    pub fn classify(&self, number: Int) -> Classification {
+        let divisors: Vec<u128> = self.0.iter().map(|rule| rule.divisor).collect();
+        let label: String = self
+            .0
+            .iter()
+            .filter(|rule| number.is_multiple_of(rule.divisor))
+            .map(|rule| rule.label.as_str())
+            .collect();
+        Classification {
+            number,
+            divisors: divisors_of(number, &divisors),
+            label: Some(label).filter(|label| !label.is_empty()),
+        }
+    }
+}
+
+/// What the rules make of one number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub number: Int,
+    /// The divisors of the rules that match, in the order of the rules.
+    pub divisors: Vec<u128>,
+    /// The labels of those rules joined, `None` when no rule matches.
+    pub label: Option<String>,
+}
+
+impl Classification {
+    pub const CSV_HEADER: &'static str = "number,label,divisors";
+
+    /// The classification as a line of CSV under `CSV_HEADER`, the divisors separated by spaces.
+    pub fn csv_record(&self) -> String {
+        let divisors: Vec<String> = self.divisors.iter().map(u128::to_string).collect();
+        format!(
+            "{},{},{}",
+            self.number,
+            csv_field(self.label.as_deref().unwrap_or_default()),
+            divisors.join(" ")
+        )
+    }
+}
+
+/// The label, or the number itself when there is none, as FizzBuzz prints it.
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.label {
+            Some(label) => f.write_str(label),
+            None => write!(f, "{}", self.number),
+        }
+    }
+}
+
+/// `field` quoted, if it has to be, the way RFC 4180 does it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// How `fizzbuzz` prints its results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One label, or number, per line.
+    #[default]
+    Text,
+    /// A header, then every number with its label and divisors.
+    Csv,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(rule: &str) -> Rule {
+        rule.parse().unwrap()
+    }
+
+    #[test]
+    fn finds_every_divisor_that_divides() {
+        let cases: [(Int, &[u128], &[u128]); 8] = [
+            (12.into(), &IF_EXPRESSION_DIVISORS, &[4, 3, 2]),
+            (9.into(), &IF_EXPRESSION_DIVISORS, &[3]),
+            (7.into(), &IF_EXPRESSION_DIVISORS, &[]),
+            // only 0 is a multiple of 0, and 0 is a multiple of everything.
+            (5.into(), &[0, 5], &[5]),
+            (0.into(), &[0, 3, 7], &[0, 3, 7]),
+            ((-12).into(), &[4, 3, 2, 5], &[4, 3, 2]),
+            (i128::MIN.into(), &[2, 3], &[2]),
+            (u128::MAX.into(), &[3, 5, 17, 2], &[3, 5, 17]),
+        ];
+        for (number, divisors, expected) in cases {
+            assert_eq!(divisors_of(number, divisors), expected, "{number}");
+        }
+    }
+
+    #[test]
+    fn joins_the_labels_of_every_matching_rule() {
+        let rules = Rules::new(vec![rule("2=Fizz"), rule("3=Buzz"), rule("6=Bazz")]);
+        let classify = |number: i32| rules.classify(number.into());
+
+        let twelve = classify(12);
+        assert_eq!(twelve.label.as_deref(), Some("FizzBuzzBazz"));
+        assert_eq!(twelve.divisors, [2, 3, 6]);
+        assert_eq!(classify(-9).to_string(), "Buzz");
+
+        let seven = classify(7);
+        assert_eq!(seven.label, None);
+        assert!(seven.divisors.is_empty());
+        assert_eq!(seven.to_string(), "7");
+
+        let fizzbuzz: Vec<String> = range(1.into(), 15.into())
+            .map(|number| Rules::fizzbuzz().classify(number).to_string())
+            .collect();
+        assert_eq!(fizzbuzz[2..6], ["Fizz", "4", "Buzz", "Fizz"]);
+        assert_eq!(fizzbuzz[14], "FizzBuzz");
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(rule(" 3 =Fizz").to_string(), "3=Fizz");
+        assert_eq!(rule("2=a=b").label, "a=b");
+        for invalid in ["3", "x=Fizz", "-3=Fizz", "3="] {
+            assert!(invalid.parse::<Rule>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn quotes_labels_that_csv_would_split() {
+        let cases = [
+            ("3=Fizz", "3,Fizz,3"),
+            ("3=a,b", "3,\"a,b\",3"),
+            ("3=say \"hi\"", "3,\"say \"\"hi\"\"\",3"),
+            ("3=plain 'quotes'", "3,plain 'quotes',3"),
+        ];
+        for (rule_text, expected) in cases {
+            let rules = Rules::new(vec![rule(rule_text)]);
+            assert_eq!(rules.classify(3.into()).csv_record(), expected);
+        }
+        let none = Rules::fizzbuzz().classify((-7).into());
+        assert_eq!(none.csv_record(), "-7,,");
+    }
+
+    #[test]
+    fn ranges_stop_at_their_end() {
+        let numbers: Vec<Int> = range((-2).into(), 1.into()).collect();
+        assert_eq!(numbers, [(-2).into(), (-1).into(), 0.into(), 1.into()]);
+        assert_eq!(range(5.into(), 4.into()).count(), 0);
+
+        // there is nothing past u128::MAX, so the last number ends the range instead of
+        // overflowing.
+        let top: Vec<Int> = range((u128::MAX - 2).into(), u128::MAX.into()).collect();
+        assert_eq!(
+            top,
+            [u128::MAX - 2, u128::MAX - 1, u128::MAX].map(Int::from)
+        );
+        assert_eq!(range(i128::MIN.into(), (i128::MIN + 1).into()).count(), 2);
+    }
+}
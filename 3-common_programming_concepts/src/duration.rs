@@ -0,0 +1,236 @@
+//! Durations written the way `THREE_HOURS_IN_SECONDS` means them: `hours(3)` instead of
+//! `60 * 60 * 3`.
+//!
+//! The constructors are `const fn`, so they work in constants. Overflowing the `u64` of seconds
+//! panics, which in a constant fails the build:
+//!
+//! ```compile_fail
+//! use common_programming_concepts::duration::{hours, Span};
+//!
+//! const TOO_LONG: Span = hours(u64::MAX);
+//! ```
+//!
+//! Spans parse from and print as human strings such as `3h 15m`, and convert to and from
+//! `std::time::Duration`.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// The units of the human form, largest first.
+const UNITS: [(&str, u64); 4] = [("d", DAY), ("h", HOUR), ("m", MINUTE), ("s", 1)];
+
+/// A whole number of seconds.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span(u64);
+
+const fn times(count: u64, unit: u64) -> Span {
+    match count.checked_mul(unit) {
+        Some(secs) => Span(secs),
+        None => panic!("the span overflows u64 seconds"),
+    }
+}
+
+pub const fn seconds(count: u64) -> Span {
+    Span(count)
+}
+
+pub const fn minutes(count: u64) -> Span {
+    times(count, MINUTE)
+}
+
+pub const fn hours(count: u64) -> Span {
+    times(count, HOUR)
+}
+
+pub const fn days(count: u64) -> Span {
+    times(count, DAY)
+}
+
+impl Span {
+    pub const fn as_secs(self) -> u64 {
+        self.0
+    }
+
+    /// `self` and `other` together, as in `hours(3).plus(minutes(15))`.
+    pub const fn plus(self, other: Span) -> Span {
+        match self.0.checked_add(other.0) {
+            Some(secs) => Span(secs),
+            None => panic!("the span overflows u64 seconds"),
+        }
+    }
+
+    pub const fn to_std(self) -> Duration {
+        Duration::from_secs(self.0)
+    }
+}
+
+impl From<Span> for Duration {
+    fn from(span: Span) -> Duration {
+        span.to_std()
+    }
+}
+
+impl TryFrom<Duration> for Span {
+    type Error = String;
+
+    /// Fails for a duration with a fraction of a second, which a span can't hold.
+    fn try_from(duration: Duration) -> Result<Span, String> {
+        if duration.subsec_nanos() != 0 {
+            return Err(format!("{duration:?} is not a whole number of seconds"));
+        }
+        Ok(Span(duration.as_secs()))
+    }
+}
+
+/// The largest units first, leaving out those that are zero: `3h 15m`, or `0s`.
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("0s");
+        }
+        let mut left = self.0;
+        let mut parts = Vec::new();
+        for (name, unit) in UNITS {
+            if left >= unit {
+                parts.push(format!("{}{name}", left / unit));
+                left %= unit;
+            }
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+impl FromStr for Span {
+    type Err = String;
+
+    /// Reads counts of days, hours, minutes and seconds, such as `3h 15m`, `90m` or `1d12h`,
+    /// each unit at most once and the largest first.
+    fn from_str(s: &str) -> Result<Span, String> {
+        let mut rest = s.trim();
+        if rest.is_empty() {
+            return Err("empty duration".to_string());
+        }
+
+        let mut total = Span(0);
+        // units not yet used; every part must use one of them.
+        let mut units = &UNITS[..];
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(|| format!("'{rest}' has no unit (d, h, m or s)"))?;
+            if digits == 0 {
+                return Err(format!("expected a number at '{rest}'"));
+            }
+            let count: u64 = rest[..digits]
+                .parse()
+                .map_err(|_| format!("{} is too large", &rest[..digits]))?;
+
+            let after = rest[digits..].trim_start();
+            let name_len = after
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(after.len());
+            let name = &after[..name_len];
+            let position = units
+                .iter()
+                .position(|&(unit, _)| unit == name)
+                .ok_or_else(|| {
+                    if UNITS.iter().any(|&(unit, _)| unit == name) {
+                        format!("'{name}' comes after a smaller unit or twice")
+                    } else {
+                        format!("unknown unit '{name}' (expected d, h, m or s)")
+                    }
+                })?;
+
+            let part = count
+                .checked_mul(units[position].1)
+                .ok_or_else(|| format!("{s} overflows u64 seconds"))?;
+            total = total
+                .0
+                .checked_add(part)
+                .map(Span)
+                .ok_or_else(|| format!("{s} overflows u64 seconds"))?;
+            units = &units[position + 1..];
+            rest = after[name_len..].trim_start();
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_human_spans() {
+        let cases = [
+            ("3h 15m", hours(3).plus(minutes(15))),
+            ("90m", minutes(90)),
+            ("1d12h", days(1).plus(hours(12))),
+            ("  3 h  15 m ", hours(3).plus(minutes(15))),
+            (
+                "1d 2h 3m 4s",
+                days(1).plus(hours(2)).plus(minutes(3)).plus(seconds(4)),
+            ),
+            ("0s", seconds(0)),
+            ("18446744073709551615s", seconds(u64::MAX)),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(text.parse(), Ok(expected), "{text}");
+        }
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_span() {
+        let cases = [
+            // out of order, or twice.
+            "15m 3h",
+            "3h3h",
+            // a number without a unit, a unit without a number, or an unknown unit.
+            "3",
+            "3h 15",
+            "h",
+            "3x",
+            "3hours",
+            "",
+            "   ",
+            "-3h",
+            // more seconds than a u64 holds.
+            "18446744073709551616s",
+            "213503982334602d",
+            "213503982334601d 8h",
+        ];
+        for text in cases {
+            assert!(text.parse::<Span>().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn display_round_trips_through_parse() {
+        let spans = [
+            seconds(0),
+            seconds(59),
+            minutes(60),
+            hours(3).plus(minutes(15)),
+            days(1).plus(seconds(1)),
+            seconds(u64::MAX),
+        ];
+        for span in spans {
+            assert_eq!(span.to_string().parse(), Ok(span), "{span}");
+        }
+        assert_eq!(minutes(90).to_string(), "1h 30m");
+        assert_eq!(seconds(0).to_string(), "0s");
+    }
+
+    #[test]
+    fn converts_to_and_from_std_durations() {
+        assert_eq!(Duration::from(hours(1)), Duration::from_secs(3600));
+        assert_eq!(
+            Span::try_from(Duration::from_secs(90)),
+            Ok(minutes(1).plus(seconds(30)))
+        );
+        assert!(Span::try_from(Duration::from_millis(1500)).is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! The integer types of the table in `scalar_types`, measured at runtime rather than written
+//! down: bit widths from `BITS`, bounds from `MIN` and `MAX`.
+
+use crate::literal;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// Runs `$body` with `$t` naming the primitive type behind `$kind`.
+macro_rules! with_type {
+    ($kind:expr, $t:ident => $body:expr) => {
+        match $kind {
+            $crate::int_types::IntType::I8 => {
+                type $t = i8;
+                $body
+            }
+            $crate::int_types::IntType::U8 => {
+                type $t = u8;
+                $body
+            }
+            $crate::int_types::IntType::I16 => {
+                type $t = i16;
+                $body
+            }
+            $crate::int_types::IntType::U16 => {
+                type $t = u16;
+                $body
+            }
+            $crate::int_types::IntType::I32 => {
+                type $t = i32;
+                $body
+            }
+            $crate::int_types::IntType::U32 => {
+                type $t = u32;
+                $body
+            }
+            $crate::int_types::IntType::I64 => {
+                type $t = i64;
+                $body
+            }
+            $crate::int_types::IntType::U64 => {
+                type $t = u64;
+                $body
+            }
+            $crate::int_types::IntType::I128 => {
+                type $t = i128;
+                $body
+            }
+            $crate::int_types::IntType::U128 => {
+                type $t = u128;
+                $body
+            }
+            $crate::int_types::IntType::Isize => {
+                type $t = isize;
+                $body
+            }
+            $crate::int_types::IntType::Usize => {
+                type $t = usize;
+                $body
+            }
+        }
+    };
+}
+pub(crate) use with_type;
+
+/// One of the primitive integer types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    I128,
+    U128,
+    Isize,
+    Usize,
+}
+
+impl IntType {
+    /// Every type, in the order of the table: by length, signed first.
+    pub const ALL: [IntType; 12] = [
+        IntType::I8,
+        IntType::U8,
+        IntType::I16,
+        IntType::U16,
+        IntType::I32,
+        IntType::U32,
+        IntType::I64,
+        IntType::U64,
+        IntType::I128,
+        IntType::U128,
+        IntType::Isize,
+        IntType::Usize,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::I8 => "i8",
+            IntType::U8 => "u8",
+            IntType::I16 => "i16",
+            IntType::U16 => "u16",
+            IntType::I32 => "i32",
+            IntType::U32 => "u32",
+            IntType::I64 => "i64",
+            IntType::U64 => "u64",
+            IntType::I128 => "i128",
+            IntType::U128 => "u128",
+            IntType::Isize => "isize",
+            IntType::Usize => "usize",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        with_type!(self, T => T::BITS)
+    }
+
+    pub fn min(self) -> Int {
+        with_type!(self, T => Int::from(T::MIN))
+    }
+
+    pub fn max(self) -> Int {
+        with_type!(self, T => Int::from(T::MAX))
+    }
+
+    /// Whether `value` is between `min` and `max` of this type.
+    pub fn holds(self, value: Int) -> bool {
+        self.min() <= value && value <= self.max()
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for IntType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<IntType, String> {
+        IntType::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| format!("unknown integer type '{s}'"))
+    }
+}
+
+/// The pointer width of the architecture this runs on, which is the width of `isize` and
+/// `usize`.
+pub fn pointer_width() -> u32 {
+    usize::BITS
+}
+
+/// An integer of any of the types, from `i128::MIN` to `u128::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Int(Repr);
+
+/// Negative values are always `Negative`, so that the derived order is the numeric one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Repr {
+    Negative(i128),
+    NonNegative(u128),
+}
+
+impl Int {
+    /// The value as a `T`, if it fits.
+    pub fn to<T: TryFrom<i128> + TryFrom<u128>>(self) -> Option<T> {
+        match self.0 {
+            Repr::Negative(value) => T::try_from(value).ok(),
+            Repr::NonNegative(value) => T::try_from(value).ok(),
+        }
+    }
+
+    /// Whether `divisor` divides the value. As for the `is_multiple_of` of the unsigned types,
+    /// only 0 is a multiple of 0.
+    pub fn is_multiple_of(self, divisor: u128) -> bool {
+        let magnitude = match self.0 {
+            Repr::Negative(value) => value.unsigned_abs(),
+            Repr::NonNegative(value) => value,
+        };
+        magnitude.is_multiple_of(divisor)
+    }
+
+    /// `self + rhs`, or `None` past `u128::MAX`.
+    pub fn checked_add_unsigned(self, rhs: u128) -> Option<Int> {
+        match self.0 {
+            Repr::Negative(value) if rhs >= value.unsigned_abs() => {
+                Some(Int(Repr::NonNegative(rhs - value.unsigned_abs())))
+            }
+            // |value| <= 2^127, so rhs fits in an i128 here.
+            Repr::Negative(value) => Some(Int(Repr::Negative(value + rhs as i128))),
+            Repr::NonNegative(value) => value
+                .checked_add(rhs)
+                .map(|sum| Int(Repr::NonNegative(sum))),
+        }
+    }
+}
+
+impl PartialOrd for Int {
+    fn partial_cmp(&self, other: &Int) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Int {
+    fn cmp(&self, other: &Int) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+macro_rules! int_from {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Int {
+            #[allow(unused_comparisons)]
+            fn from(value: $t) -> Int {
+                if value < 0 {
+                    Int(Repr::Negative(value as i128))
+                } else {
+                    Int(Repr::NonNegative(value as u128))
+                }
+            }
+        }
+    )*};
+}
+int_from!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize);
+
+impl fmt::Display for Int {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Repr::Negative(value) => fmt::Display::fmt(&value, f),
+            Repr::NonNegative(value) => fmt::Display::fmt(&value, f),
+        }
+    }
+}
+
+impl FromStr for Int {
+    type Err = String;
+
+    /// Reads any integer literal, see `literal::parse_int`.
+    fn from_str(s: &str) -> Result<Int, String> {
+        literal::parse_int(s).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_type_holds_its_bounds_and_nothing_past_them() {
+        for kind in IntType::ALL {
+            let (min, max) = (kind.min(), kind.max());
+            assert!(kind.holds(min) && kind.holds(max), "{kind}");
+            // the neighbours of the bounds, where `Int` has any.
+            let below = min.to::<i128>().and_then(|min| min.checked_sub(1));
+            if let Some(below) = below {
+                assert!(!kind.holds(below.into()), "{kind} holds {below}");
+            }
+            if let Some(above) = max.checked_add_unsigned(1) {
+                assert!(!kind.holds(above), "{kind} holds {above}");
+            }
+        }
+        assert!(!IntType::U8.holds((-1).into()));
+        assert!(!IntType::I128.holds((i128::MAX as u128 + 1).into()));
+        assert!(IntType::U128.holds(u128::MAX.into()));
+    }
+
+    #[test]
+    fn orders_negative_values_below_every_unsigned_one() {
+        let ascending: [Int; 8] = [
+            i128::MIN.into(),
+            (-1i128).into(),
+            0u128.into(),
+            1i8.into(),
+            (i128::MAX as u128).into(),
+            (i128::MAX as u128 + 1).into(),
+            (u128::MAX - 1).into(),
+            u128::MAX.into(),
+        ];
+        for pair in ascending.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+        // the same value from a signed and an unsigned type is one `Int`.
+        assert_eq!(Int::from(5i8), Int::from(5u128));
+        assert_eq!(Int::from(0i128), Int::from(0u8));
+    }
+
+    #[test]
+    fn adds_up_to_u128_max() {
+        let add = |value: Int, rhs| value.checked_add_unsigned(rhs);
+        assert_eq!(add(u128::MAX.into(), 0), Some(u128::MAX.into()));
+        assert_eq!(add((u128::MAX - 1).into(), 1), Some(u128::MAX.into()));
+        assert_eq!(add(u128::MAX.into(), 1), None);
+        assert_eq!(add(1u8.into(), u128::MAX), None);
+        // from the most negative value, to the largest one there is.
+        assert_eq!(
+            add(i128::MIN.into(), u128::MAX),
+            Some((i128::MAX as u128).into())
+        );
+        assert_eq!(add(i128::MIN.into(), 1 << 127), Some(0u8.into()));
+        assert_eq!(add((-5i8).into(), 3), Some((-2i8).into()));
+        assert_eq!(add((-5i8).into(), 5), Some(0u8.into()));
+    }
+}
@@ -0,0 +1,13 @@
+//! Runnable versions of what the chapter 3 notes in `main.rs` only describe in comments.
+//!
+//! Running the binary without arguments still walks through the chapter; the commands parsed by
+//! `config` explore one topic each.
+
+pub mod config;
+pub mod countdown;
+pub mod divisibility;
+pub mod duration;
+pub mod int_types;
+pub mod literal;
+pub mod math;
+pub mod overflow;
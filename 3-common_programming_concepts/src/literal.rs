@@ -0,0 +1,493 @@
+//! Parses number literals in every form of the table in `scalar_types`: decimal `98_222`, hex
+//! `0xff`, octal `0o77`, binary `0b1111_0000`, byte `b'A'`, floats such as `2.5` and `1e10`,
+//! and type suffixes such as `57u8` or `1e10f32`.
+//!
+//! The rules are the compiler's: an integer without a suffix is an `i32` and a float an `f64`,
+//! a value out of range for its type is an error (rustc's `overflowing_literals`), and suffixes
+//! are only read where they cannot be digits, so `0x1f32` is the hex number 0x1f32. A leading
+//! `-` is accepted and negates the value, as the unary minus in front of a literal would.
+
+use crate::int_types::{Int, IntType};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatType {
+    F32,
+    F64,
+}
+
+impl FloatType {
+    pub fn name(self) -> &'static str {
+        match self {
+            FloatType::F32 => "f32",
+            FloatType::F64 => "f64",
+        }
+    }
+}
+
+impl fmt::Display for FloatType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A parsed literal with its type; `suffixed` is false when the type is the default one, and
+/// true for a suffix or a byte literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Literal {
+    Int {
+        value: Int,
+        kind: IntType,
+        suffixed: bool,
+    },
+    /// The value of an `f32` is kept as the `f64` it converts to.
+    Float {
+        value: f64,
+        kind: FloatType,
+        suffixed: bool,
+    },
+}
+
+impl Literal {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Literal::Int { kind, .. } => kind.name(),
+            Literal::Float { kind, .. } => kind.name(),
+        }
+    }
+
+    pub fn is_suffixed(&self) -> bool {
+        match *self {
+            Literal::Int { suffixed, .. } | Literal::Float { suffixed, .. } => suffixed,
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Literal::Int { value, .. } => write!(f, "{value}"),
+            Literal::Float {
+                value,
+                kind: FloatType::F32,
+                ..
+            } => write!(f, "{:?}", value as f32),
+            Literal::Float { value, .. } => write!(f, "{value:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    Empty,
+    /// Not a literal at all, such as a digit of the wrong base or an unknown suffix.
+    Invalid(String),
+    /// The value does not fit in its type, given by the suffix or the default one.
+    OutOfRange {
+        literal: String,
+        type_name: &'static str,
+    },
+    /// A `-` in front of an unsigned literal.
+    NegativeUnsigned(IntType),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralError::Empty => f.write_str("empty literal"),
+            LiteralError::Invalid(reason) => f.write_str(reason),
+            LiteralError::OutOfRange { literal, type_name } => {
+                write!(f, "literal out of range for {type_name}: {literal}")
+            }
+            LiteralError::NegativeUnsigned(kind) => write!(f, "cannot negate the unsigned {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+/// A literal split up, before the default type is applied to an integer.
+enum Parts {
+    Int {
+        negative: bool,
+        /// `None` when the digits don't even fit in a `u128`.
+        magnitude: Option<u128>,
+        suffix: Option<IntType>,
+    },
+    Float(Literal),
+}
+
+/// Parses `literal`, giving an integer without a suffix the type `i32` and a float `f64`.
+pub fn parse(literal: &str) -> Result<Literal, LiteralError> {
+    let literal = literal.trim();
+    match split(literal)? {
+        Parts::Float(float) => Ok(float),
+        Parts::Int {
+            negative,
+            magnitude,
+            suffix,
+        } => {
+            let kind = suffix.unwrap_or(IntType::I32);
+            let value = magnitude
+                .and_then(|magnitude| signed(negative, magnitude))
+                .filter(|&value| kind.holds(value))
+                .ok_or_else(|| out_of_range(literal, kind.name()))?;
+            Ok(Literal::Int {
+                value,
+                kind,
+                suffixed: suffix.is_some(),
+            })
+        }
+    }
+}
+
+/// Parses an integer literal. Unlike `parse`, a literal without a suffix is not held to `i32`
+/// but may be anything from `i128::MIN` to `u128::MAX`.
+pub fn parse_int(literal: &str) -> Result<Int, LiteralError> {
+    let literal = literal.trim();
+    match split(literal)? {
+        Parts::Float(_) => Err(LiteralError::Invalid(format!(
+            "{literal} is a float, not an integer"
+        ))),
+        Parts::Int {
+            negative,
+            magnitude,
+            suffix,
+        } => {
+            let widest = if negative { "i128" } else { "u128" };
+            let value = magnitude
+                .and_then(|magnitude| signed(negative, magnitude))
+                .ok_or_else(|| out_of_range(literal, widest))?;
+            match suffix {
+                Some(kind) if !kind.holds(value) => Err(out_of_range(literal, kind.name())),
+                _ => Ok(value),
+            }
+        }
+    }
+}
+
+fn out_of_range(literal: &str, type_name: &'static str) -> LiteralError {
+    LiteralError::OutOfRange {
+        literal: literal.to_string(),
+        type_name,
+    }
+}
+
+/// `magnitude` with its sign, if some integer type can hold it.
+fn signed(negative: bool, magnitude: u128) -> Option<Int> {
+    match (negative, i128::try_from(magnitude)) {
+        (false, _) => Some(Int::from(magnitude)),
+        (true, Ok(magnitude)) => Some(Int::from(-magnitude)),
+        (true, Err(_)) if magnitude == i128::MIN.unsigned_abs() => Some(Int::from(i128::MIN)),
+        (true, Err(_)) => None,
+    }
+}
+
+fn invalid(reason: String) -> LiteralError {
+    LiteralError::Invalid(reason)
+}
+
+fn split(literal: &str) -> Result<Parts, LiteralError> {
+    let (negative, body) = match literal.strip_prefix('-') {
+        Some(body) => (true, body.trim_start()),
+        None => (false, literal),
+    };
+    if body.is_empty() {
+        return Err(LiteralError::Empty);
+    }
+
+    if let Some(byte) = body.strip_prefix("b'") {
+        if negative {
+            return Err(LiteralError::NegativeUnsigned(IntType::U8));
+        }
+        return Ok(Parts::Int {
+            negative,
+            magnitude: Some(u128::from(byte_literal(byte)?)),
+            suffix: Some(IntType::U8),
+        });
+    }
+
+    let (radix, body) = match body.get(..2) {
+        Some("0x") => (16, &body[2..]),
+        Some("0o") => (8, &body[2..]),
+        Some("0b") => (2, &body[2..]),
+        _ => (10, body),
+    };
+    // `_1` would be an identifier; after a prefix, `0x_1` is fine.
+    if radix == 10 && body.starts_with('_') {
+        return Err(invalid(format!("{literal} is not a literal")));
+    }
+    let (digits, rest) = take_digits(body, radix);
+    if !digits.chars().any(|c| c != '_') {
+        return Err(invalid(format!("{literal} has no digits")));
+    }
+    if let Some(digit) = rest.chars().next().filter(char::is_ascii_digit) {
+        return Err(invalid(format!(
+            "invalid digit '{digit}' for a base {radix} literal"
+        )));
+    }
+
+    let is_float = rest.starts_with(['.', 'e', 'E']) || rest == "f32" || rest == "f64";
+    match (radix, is_float) {
+        (10, true) => float(literal, negative, digits, rest).map(Parts::Float),
+        (_, true) => Err(invalid(format!(
+            "base {radix} float literals are not supported"
+        ))),
+        (_, false) => {
+            let suffix = match rest {
+                "" => None,
+                suffix => Some(
+                    suffix
+                        .parse::<IntType>()
+                        .map_err(|_| invalid(format!("invalid suffix '{suffix}'")))?,
+                ),
+            };
+            if let (true, Some(kind)) = (negative, suffix) {
+                if kind.min() == Int::from(0) {
+                    return Err(LiteralError::NegativeUnsigned(kind));
+                }
+            }
+            Ok(Parts::Int {
+                negative,
+                magnitude: magnitude(digits, radix),
+                suffix,
+            })
+        }
+    }
+}
+
+/// Splits `s` after its leading digits of `radix` and `_` separators.
+fn take_digits(s: &str, radix: u32) -> (&str, &str) {
+    let end = s
+        .find(|c: char| c != '_' && !c.is_digit(radix))
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn magnitude(digits: &str, radix: u32) -> Option<u128> {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(radix))
+        .try_fold(0u128, |value, digit| {
+            value
+                .checked_mul(u128::from(radix))?
+                .checked_add(u128::from(digit))
+        })
+}
+
+/// The rest of a decimal float after its integer digits: `.fraction`, `e[+-]exponent` and a
+/// suffix, each optional.
+fn float(literal: &str, negative: bool, digits: &str, rest: &str) -> Result<Literal, LiteralError> {
+    let mut number = String::from(if negative { "-" } else { "" });
+    number.extend(digits.chars().filter(|&c| c != '_'));
+    let mut rest = rest;
+
+    if let Some(after_dot) = rest.strip_prefix('.') {
+        let (fraction, after) = take_digits(after_dot, 10);
+        // `1._5` and `1.e3` are field accesses, not floats.
+        if after_dot.starts_with(|c: char| !c.is_ascii_digit()) {
+            return Err(invalid(format!("{literal} is not a literal")));
+        }
+        number.push('.');
+        number.extend(fraction.chars().filter(|&c| c != '_'));
+        rest = after;
+    }
+
+    if let Some(after_e) = rest.strip_prefix(['e', 'E']) {
+        let (sign, after_sign) = match after_e.strip_prefix(['+', '-']) {
+            Some(after_sign) => (&after_e[..1], after_sign),
+            None => ("", after_e),
+        };
+        let (exponent, after) = take_digits(after_sign, 10);
+        if !exponent.chars().any(|c| c != '_') {
+            return Err(invalid(format!("{literal} has an exponent without digits")));
+        }
+        number.push('e');
+        number.push_str(sign);
+        number.extend(exponent.chars().filter(|&c| c != '_'));
+        rest = after;
+    }
+
+    let (kind, suffixed) = match rest {
+        "" => (FloatType::F64, false),
+        "f32" => (FloatType::F32, true),
+        "f64" => (FloatType::F64, true),
+        suffix if suffix.parse::<IntType>().is_ok() => {
+            return Err(invalid(format!(
+                "the integer suffix '{suffix}' can't be used on a float"
+            )))
+        }
+        suffix => return Err(invalid(format!("invalid suffix '{suffix}'"))),
+    };
+    let value = match kind {
+        FloatType::F32 => number.parse::<f32>().map(f64::from),
+        FloatType::F64 => number.parse::<f64>(),
+    };
+    let value = value.map_err(|err| invalid(format!("{literal}: {err}")))?;
+    if value.is_infinite() {
+        return Err(out_of_range(literal, kind.name()));
+    }
+    Ok(Literal::Float {
+        value,
+        kind,
+        suffixed,
+    })
+}
+
+/// The byte of `b'A'`, given what follows `b'`: one ASCII character or escape, and the quote.
+fn byte_literal(s: &str) -> Result<u8, LiteralError> {
+    let inner = s
+        .strip_suffix('\'')
+        .ok_or_else(|| invalid(format!("b'{s} is missing its closing quote")))?;
+    let byte = match inner.as_bytes() {
+        [b'\\', b'n'] => b'\n',
+        [b'\\', b'r'] => b'\r',
+        [b'\\', b't'] => b'\t',
+        [b'\\', b'\\'] => b'\\',
+        [b'\\', b'0'] => b'\0',
+        [b'\\', b'\''] => b'\'',
+        [b'\\', b'"'] => b'"',
+        [b'\\', b'x', high, low] => {
+            let hex = std::str::from_utf8(&[*high, *low])
+                .unwrap_or_default()
+                .to_string();
+            u8::from_str_radix(&hex, 16)
+                .map_err(|_| invalid(format!("invalid escape '\\x{hex}' in a byte literal")))?
+        }
+        [byte] if byte.is_ascii() && *byte != b'\'' && *byte != b'\\' => *byte,
+        _ => {
+            return Err(invalid(format!(
+                "b'{inner}' must be a single ASCII character or escape"
+            )))
+        }
+    };
+    Ok(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(value: i128, kind: IntType, suffixed: bool) -> Result<Literal, LiteralError> {
+        Ok(Literal::Int {
+            value: Int::from(value),
+            kind,
+            suffixed,
+        })
+    }
+
+    fn float(value: f64, kind: FloatType, suffixed: bool) -> Result<Literal, LiteralError> {
+        Ok(Literal::Float {
+            value,
+            kind,
+            suffixed,
+        })
+    }
+
+    fn out_of_range(literal: &str, type_name: &'static str) -> Result<Literal, LiteralError> {
+        Err(super::out_of_range(literal, type_name))
+    }
+
+    fn is_invalid(result: Result<Literal, LiteralError>) -> bool {
+        matches!(result, Err(LiteralError::Invalid(_)))
+    }
+
+    #[test]
+    fn parses_every_form_with_its_type() {
+        let cases = [
+            ("98_222", int(98_222, IntType::I32, false)),
+            ("0xff", int(255, IntType::I32, false)),
+            ("0o77", int(63, IntType::I32, false)),
+            ("0b1111_0000", int(240, IntType::I32, false)),
+            ("0x_ff", int(255, IntType::I32, false)),
+            ("b'A'", int(65, IntType::U8, true)),
+            ("b'\\n'", int(10, IntType::U8, true)),
+            ("b'\\xff'", int(255, IntType::U8, true)),
+            ("57u8", int(57, IntType::U8, true)),
+            ("1_000i64", int(1_000, IntType::I64, true)),
+            // hex digits are read first, so this is not an f32.
+            ("0x1f32", int(0x1f32, IntType::I32, false)),
+            ("-0x80i8", int(-128, IntType::I8, true)),
+            ("-2147483648", int(i32::MIN.into(), IntType::I32, false)),
+            ("2.5", float(2.5, FloatType::F64, false)),
+            ("1.", float(1.0, FloatType::F64, false)),
+            ("1e10", float(1e10, FloatType::F64, false)),
+            ("1E-3", float(1e-3, FloatType::F64, false)),
+            ("1e10f32", float(1e10, FloatType::F32, true)),
+            ("2f64", float(2.0, FloatType::F64, true)),
+            ("-1_000.5", float(-1_000.5, FloatType::F64, false)),
+        ];
+        for (literal, expected) in cases {
+            assert_eq!(parse(literal), expected, "{literal}");
+        }
+    }
+
+    #[test]
+    fn rejects_values_out_of_range_for_their_type() {
+        let cases = [
+            ("2147483648", out_of_range("2147483648", "i32")),
+            ("256u8", out_of_range("256u8", "u8")),
+            ("-0x81i8", out_of_range("-0x81i8", "i8")),
+            ("1e400", out_of_range("1e400", "f64")),
+            ("1e39f32", out_of_range("1e39f32", "f32")),
+            (
+                "340282366920938463463374607431768211456u128",
+                out_of_range("340282366920938463463374607431768211456u128", "u128"),
+            ),
+        ];
+        for (literal, expected) in cases {
+            assert_eq!(parse(literal), expected, "{literal}");
+        }
+    }
+
+    #[test]
+    fn rejects_what_is_not_a_literal() {
+        assert_eq!(parse(""), Err(LiteralError::Empty));
+        assert_eq!(parse("-"), Err(LiteralError::Empty));
+        assert_eq!(
+            parse("-5u8"),
+            Err(LiteralError::NegativeUnsigned(IntType::U8))
+        );
+        assert_eq!(
+            parse("-b'A'"),
+            Err(LiteralError::NegativeUnsigned(IntType::U8))
+        );
+
+        // field accesses rather than floats, integer suffixes on floats, digits of the wrong
+        // base or none at all, unknown suffixes and bad bytes.
+        let invalid = [
+            "1._5", "1.e3", "1.5u8", "1e3i32", "0b102", "0o8", "0x", "1e", "_1", "12abc", "1.5f16",
+            "0x1p3", "b'ab'", "b'A", "b'\\q'", "b'é'",
+        ];
+        for literal in invalid {
+            assert!(
+                is_invalid(parse(literal)),
+                "{literal}: {:?}",
+                parse(literal)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_int_takes_any_integer_without_a_suffix() {
+        assert_eq!(
+            parse_int("340282366920938463463374607431768211455"),
+            Ok(Int::from(u128::MAX))
+        );
+        assert_eq!(
+            parse_int("-170141183460469231731687303715884105728"),
+            Ok(Int::from(i128::MIN))
+        );
+        assert_eq!(parse_int("0xff_ff"), Ok(Int::from(0xffff)));
+        assert_eq!(parse_int("-0x80i8"), Ok(Int::from(-128)));
+        assert_eq!(
+            parse_int("-170141183460469231731687303715884105729"),
+            Err(super::out_of_range(
+                "-170141183460469231731687303715884105729",
+                "i128"
+            ))
+        );
+        assert_eq!(parse_int("300u8"), Err(super::out_of_range("300u8", "u8")));
+        assert!(matches!(parse_int("1.5"), Err(LiteralError::Invalid(_))));
+    }
+}
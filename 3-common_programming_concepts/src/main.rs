@@ -1,4 +1,49 @@
+use common_programming_concepts::{
+    config::Command,
+    countdown::{Clock, Countdown, Event, FakeClock, Outcome, SystemClock},
+    divisibility::{self, Classification, Format, Rules},
+    duration::{self, Span},
+    int_types::{self, Int, IntType},
+    literal,
+    math::{self, Bench},
+    overflow::{self, Op, Panic},
+};
+use num_bigint::BigUint;
+use std::{env, fmt::Display, io, process, sync::mpsc, thread};
+
 fn main() {
+    let command = Command::build(env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    match command {
+        Command::Chapters => chapters(),
+        Command::Types { value } => types(value),
+        Command::Overflow { op, lhs, rhs, kind } => overflow(op, kind, lhs, rhs),
+        Command::OverflowEdgeCases => overflow_edge_cases(),
+        Command::Literal { literals } => literals.iter().for_each(|text| literal(text)),
+        Command::Factorial { n } => factorial(n),
+        Command::Fibonacci { n } => fibonacci(n),
+        Command::Binomial { n, k } => binomial(n, k),
+        Command::MathBench => math_bench(),
+        Command::SafeDepth { stack_size } => safe_depth(stack_size),
+        Command::Duration { span } => show_duration(span),
+        Command::Divisible { number, divisors } => divisible(number, &divisors),
+        Command::FizzBuzz {
+            start,
+            end,
+            rules,
+            format,
+        } => fizzbuzz(start, end, &rules, format),
+        Command::Countdown {
+            countdown,
+            fake_clock,
+        } => run_countdown(&countdown, fake_clock),
+    }
+}
+
+fn chapters() {
     println!("\n# 3.1 Variables and Mutability");
     variables();
     constants();
@@ -44,6 +89,10 @@ fn variables() {
 }
 
 const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
+// const fn calls can be evaluated at compile time, so they can give a constant its value too;
+// if the call panics (here on overflow), the program doesn't compile.
+const THREE_HOURS: Span = duration::hours(3);
+const MOVIE: Span = duration::hours(1).plus(duration::minutes(30));
 fn constants() {
     println!("\n## Constants");
 
@@ -52,6 +101,7 @@ fn constants() {
     // can't be declared with a value that could be computed at runtime.
 
     println!("Three hours in second is {THREE_HOURS_IN_SECONDS}");
+    println!("Three hours is {THREE_HOURS}, and a movie {MOVIE}");
 }
 
 fn shadowing() {
@@ -92,6 +142,9 @@ fn scalar_types() {
     // unsigned: 0 to 2n - 1
 
     // arch depend on the architecture of the computer your program is running on.
+    //
+    // `cargo run -- types` prints this table with the bounds computed at runtime, and
+    // `cargo run -- types 300` which of the types can hold 300.
 
     // Number literals can:
     // - multiple numeric types allow a type suffix
@@ -103,6 +156,9 @@ fn scalar_types() {
     // Octal              0o77
     // Binary             0b1111_0000
     // Byte (u8 only)     b'A'
+    //
+    // `cargo run -- literal 0xff 57u8 1e10f32` parses literals like these and prints the value
+    // and type of each.
 
     // The primary situation in which you'd use isize or usize is when indexing some sort of
     // collection.
@@ -117,6 +173,9 @@ fn scalar_types() {
     // - return the value and a boolean indicating whether there was overflow with the overflow_*
     // methods.
     // - saturate at the value's minimum or maximum values with the saturating_* methods.
+    //
+    // `cargo run -- overflow add 127 1 i8` shows all of them side by side, and
+    // `cargo run -- overflow edge-cases` on a table of tricky cases such as i8::MIN / -1.
 
     println!("\n#### Floating-point numbers");
     // an integer is a number with a fractional component.
@@ -220,6 +279,7 @@ fn compound_types() {
     // let _element = a[10]; // throw index out of bounds: the length is 5 but the index is 10
 }
 
+// `cargo run -- math factorial 10000` computes this without either overflow: see `math`.
 fn _stack_overflow_error() {
     fn factorial(n: u32) -> u32 {
         if n == 0 {
@@ -299,6 +359,7 @@ fn functions_with_return() {
           // error
     }
     // is same as:
+    #[allow(clippy::needless_return)]
     fn _five_2() -> i32 {
         let x: i32 = 5;
         return x;
@@ -332,6 +393,8 @@ fn if_expression() {
     } else {
         println!("number is not divisible by 4, 3 or 2");
     }
+    // the chain stops at the first true condition: 12 would only be "divisible by 4".
+    // `cargo run -- divisible 12` checks every divisor instead.
 }
 
 fn if_in_let_statement() {
@@ -404,6 +467,7 @@ fn loops_while() {
         number -= 1;
     }
     println!("LIFTOFF!!!");
+    // `cargo run -- countdown` waits a second between the numbers.
 
     // loop over the elements of a collection, such as an array.
     // however, this approach is error prone; we could cause the program to panic if the index
@@ -433,3 +497,286 @@ fn for_loop() {
     }
     println!("LIFTOFF!!!");
 }
+
+fn types(value: Option<Int>) {
+    println!("{:<6} {:>4} {:>40} {:>40}", "type", "bits", "min", "max");
+    for kind in IntType::ALL {
+        println!(
+            "{:<6} {:>4} {:>40} {:>40}",
+            kind.name(),
+            kind.bits(),
+            kind.min(),
+            kind.max()
+        );
+    }
+    println!(
+        "isize and usize are {} bits here, the pointer width of this architecture",
+        int_types::pointer_width()
+    );
+
+    if let Some(value) = value {
+        let (fits, too_big): (Vec<IntType>, Vec<IntType>) =
+            IntType::ALL.into_iter().partition(|kind| kind.holds(value));
+        let names = |kinds: Vec<IntType>| {
+            let names: Vec<&str> = kinds.into_iter().map(IntType::name).collect();
+            if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            }
+        };
+        println!("\n{value} fits in: {}", names(fits));
+        println!("{value} does not fit in: {}", names(too_big));
+    }
+}
+
+fn overflow(op: Op, kind: IntType, lhs: Int, rhs: Int) {
+    let lab = overflow::run(op, kind, lhs, rhs).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+    println!("{}\n", op.expression(kind, lhs, rhs));
+    print!("{lab}");
+}
+
+fn overflow_edge_cases() {
+    let show = |result: Result<Int, Panic>| match result {
+        Ok(value) => value.to_string(),
+        Err(_) => "panic".to_string(),
+    };
+
+    println!(
+        "{:<18} {:>12} {:>12} {:>21} {:>12} {:>12} {:>12}",
+        "case", "wrapping", "checked", "overflowing", "saturating", "debug", "release"
+    );
+    for (op, kind, lhs, rhs) in overflow::edge_cases() {
+        let lab = overflow::run(op, kind, lhs, rhs).expect("edge cases fit their types");
+        let checked = lab
+            .checked
+            .map_or("None".to_string(), |value| value.to_string());
+        let overflowing = match lab.overflowing {
+            Ok((value, overflowed)) => format!("({value}, {overflowed})"),
+            Err(_) => "panic".to_string(),
+        };
+        let saturating = lab.saturating.map_or("-".to_string(), show);
+        println!(
+            "{:<18} {:>12} {:>12} {:>21} {:>12} {:>12} {:>12}",
+            op.expression(kind, lhs, rhs),
+            show(lab.wrapping),
+            checked,
+            overflowing,
+            saturating,
+            show(lab.debug),
+            show(lab.release)
+        );
+    }
+}
+
+fn literal(text: &str) {
+    match literal::parse(text) {
+        Ok(literal) => {
+            let default = if literal.is_suffixed() {
+                ""
+            } else {
+                " (the default)"
+            };
+            println!("{text} = {literal}: {}{default}", literal.type_name());
+        }
+        Err(err) => println!("{text}: {err}"),
+    }
+}
+
+/// Prints a result of each width: the fixed ones or that they overflow, and the exact one,
+/// abridged when it is long.
+fn print_widths(as_u64: Option<impl Display>, as_u128: Option<impl Display>, exact: &BigUint) {
+    let show = |value: Option<String>| value.unwrap_or_else(|| "overflows".to_string());
+    println!(
+        "{:<10} {}",
+        "u64",
+        show(as_u64.map(|value| value.to_string()))
+    );
+    println!(
+        "{:<10} {}",
+        "u128",
+        show(as_u128.map(|value| value.to_string()))
+    );
+
+    let digits = exact.to_string();
+    if digits.len() <= 60 {
+        println!("{:<10} {digits}", "exact");
+    } else {
+        let (head, tail) = (&digits[..20], &digits[digits.len() - 20..]);
+        println!("{:<10} {head}...{tail} ({} digits)", "exact", digits.len());
+    }
+}
+
+fn factorial(n: u32) {
+    println!("{n}!");
+    let exact = math::factorial_big(n);
+    print_widths(math::factorial_u64(n), math::factorial_u128(n), &exact);
+
+    let recursive = math::with_stack(math::DEFAULT_STACK, |guard| {
+        math::factorial_recursive(n, guard)
+    });
+    match recursive.unwrap_or_else(|err| stack_failed(math::DEFAULT_STACK, err)) {
+        Ok(product) if product == exact => println!("{:<10} same, on an 8 MiB stack", "recursive"),
+        Ok(_) => println!("{:<10} differs!", "recursive"),
+        Err(err) => println!("{:<10} {err}", "recursive"),
+    }
+}
+
+fn fibonacci(n: u32) {
+    println!("fibonacci({n})");
+    print_widths(
+        math::fibonacci_u64(n),
+        math::fibonacci_u128(n),
+        &math::fibonacci_big(n),
+    );
+}
+
+fn binomial(n: u64, k: u64) {
+    println!("{n} choose {k}");
+    print_widths(
+        math::binomial_u64(n, k),
+        math::binomial_u128(n, k),
+        &math::binomial_big(n, k),
+    );
+}
+
+fn math_bench() {
+    let cases = [(10, 10_000), (100, 1_000), (1_000, 100), (10_000, 5)];
+    let (benches, depth) = math::with_stack(math::DEFAULT_STACK, |guard| {
+        let benches: Vec<Bench> = cases
+            .into_iter()
+            .map(|(n, runs)| math::benchmark(n, runs, guard))
+            .collect();
+        (benches, math::safe_depth(guard))
+    })
+    .unwrap_or_else(|err| stack_failed(math::DEFAULT_STACK, err));
+
+    println!(
+        "{:>7} {:>6} {:>14} {:>14}",
+        "n", "runs", "iterative", "recursive"
+    );
+    for bench in benches {
+        let recursive = match bench.recursive {
+            Ok(time) => format!("{time:?}"),
+            Err(err) => format!("too deep at {}", err.depth),
+        };
+        println!(
+            "{:>7} {:>6} {:>14} {:>14}",
+            bench.n,
+            bench.runs,
+            format!("{:?}", bench.iterative),
+            recursive
+        );
+    }
+    if let Some(depth) = depth {
+        println!("\nthe recursive version stops at about {depth}! on an 8 MiB stack, the iterative one never");
+    }
+}
+
+fn safe_depth(stack_size: usize) {
+    let (depth, beyond) = math::with_stack(stack_size, |guard| {
+        let depth = math::safe_depth(guard);
+        let beyond = depth.map(|depth| math::factorial_recursive(depth.saturating_mul(2), guard));
+        (depth, beyond)
+    })
+    .unwrap_or_else(|err| stack_failed(stack_size, err));
+
+    let Some(depth) = depth else {
+        println!("a stack of {stack_size} bytes is too small for any recursion");
+        return;
+    };
+    println!("with a stack of {stack_size} bytes the recursive factorial goes up to {depth}!");
+    if let Some(Err(err)) = beyond {
+        println!("{}!: {err}", depth.saturating_mul(2));
+    }
+}
+
+fn stack_failed(stack_size: usize, err: io::Error) -> ! {
+    eprintln!("Could not start a thread with a stack of {stack_size} bytes: {err}");
+    process::exit(1);
+}
+
+fn show_duration(span: Span) {
+    println!("{span}");
+    println!("{} seconds", span.as_secs());
+    println!("{:?} as a std::time::Duration", span.to_std());
+}
+
+/// `items` as `a, b and c`, or with `or` as the last word.
+fn list(items: &[u128], last: &str) -> String {
+    let items: Vec<String> = items.iter().map(u128::to_string).collect();
+    match items.split_last() {
+        None => String::new(),
+        Some((only, [])) => only.clone(),
+        Some((last_item, rest)) => format!("{} {last} {last_item}", rest.join(", ")),
+    }
+}
+
+fn divisible(number: Int, divisors: &[u128]) {
+    let matching = divisibility::divisors_of(number, divisors);
+    if matching.is_empty() {
+        println!("{number} is not divisible by {}", list(divisors, "or"));
+    } else {
+        println!("{number} is divisible by {}", list(&matching, "and"));
+    }
+}
+
+fn fizzbuzz(start: Int, end: Int, rules: &Rules, format: Format) {
+    if format == Format::Csv {
+        println!("{}", Classification::CSV_HEADER);
+    }
+    for number in divisibility::range(start, end) {
+        let classification = rules.classify(number);
+        match format {
+            Format::Text => println!("{classification}"),
+            Format::Csv => println!("{}", classification.csv_record()),
+        }
+    }
+}
+
+fn run_countdown(countdown: &Countdown, fake_clock: bool) {
+    let mut out = io::stdout();
+    let (outcome, elapsed) = if fake_clock {
+        let mut clock = FakeClock::default();
+        (countdown.run(&mut clock, &mut out), clock.now())
+    } else {
+        let (sender, events) = mpsc::channel();
+        let interrupts = sender.clone();
+        ctrlc::set_handler(move || {
+            let _ = interrupts.send(Event::Abort);
+        })
+        .expect("Failed to set the Ctrl-C handler");
+        // the thread is left blocked on stdin when the countdown ends; exiting ends it.
+        thread::spawn(move || {
+            for line in io::stdin().lines() {
+                let event = match line.as_deref().map(str::trim) {
+                    Ok("q") | Err(_) => Event::Abort,
+                    Ok(_) => Event::Toggle,
+                };
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        println!("press Enter to pause or resume, q and Enter or Ctrl-C to abort");
+        let mut clock = SystemClock::new(events);
+        (countdown.run(&mut clock, &mut out), clock.now())
+    };
+
+    match outcome {
+        Ok(Outcome::Liftoff) if fake_clock => println!("({elapsed:?} on the fake clock)"),
+        Ok(Outcome::Liftoff) => {}
+        Ok(Outcome::Aborted { at }) => {
+            println!("aborted at {at}, after {:.1}s", elapsed.as_secs_f64());
+            process::exit(130);
+        }
+        Err(err) => {
+            eprintln!("Problem writing the countdown: {err}");
+            process::exit(1);
+        }
+    }
+}
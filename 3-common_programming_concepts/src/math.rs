@@ -0,0 +1,301 @@
+//! Factorials, Fibonacci numbers and binomial coefficients without the two crashes of
+//! `_stack_overflow_error`: the recursion running out of stack and the result overflowing.
+//!
+//! The `u64` and `u128` variants are loops, the tail-recursive form with its accumulator made
+//! explicit, and answer `None` instead of overflowing. The `BigUint` variants are exact for any
+//! size. `factorial_recursive` keeps the recursion for comparison, but checks the stack it has
+//! used at every level against a `StackGuard`, so too deep a recursion is an error, not a crash.
+
+use num_bigint::BigUint;
+use std::{
+    fmt,
+    hint::black_box,
+    io, thread,
+    time::{Duration, Instant},
+};
+
+/// The stack `with_stack` gives a recursion when nothing else is asked for, the usual size of
+/// a main thread.
+pub const DEFAULT_STACK: usize = 8 * 1024 * 1024;
+/// What a `with_stack` guard keeps free for the work done on top of the deepest level, at most;
+/// small stacks keep a quarter.
+const RESERVE: usize = 64 * 1024;
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+macro_rules! checked {
+    ($t:ty, $factorial:ident, $fibonacci:ident, $binomial:ident) => {
+        #[doc = concat!("`n!`, or `None` when it does not fit in a `", stringify!($t), "`.")]
+        pub fn $factorial(n: u32) -> Option<$t> {
+            (1..=<$t>::from(n)).try_fold(1, |product: $t, i| product.checked_mul(i))
+        }
+
+        #[doc = concat!(
+            "The `n`th Fibonacci number (0, 1, 1, 2, ...), or `None` when it does not fit in a `",
+            stringify!($t),
+            "`."
+        )]
+        pub fn $fibonacci(n: u32) -> Option<$t> {
+            // fib(-1) = 1 starts the sequence without computing past fib(n).
+            let (mut previous, mut current): ($t, $t) = (1, 0);
+            for _ in 0..n {
+                (previous, current) = (current, previous.checked_add(current)?);
+            }
+            Some(current)
+        }
+
+        #[doc = concat!(
+            "`n` choose `k`, or `None` when it does not fit in a `",
+            stringify!($t),
+            "`."
+        )]
+        ///
+        /// Every intermediate value is itself a binomial coefficient no larger than the result,
+        /// so this only fails when the result overflows.
+        pub fn $binomial(n: u64, k: u64) -> Option<$t> {
+            if k > n {
+                return Some(0);
+            }
+            let k = k.min(n - k);
+            (0..k).try_fold(1, |result: $t, i| {
+                // result * (n - i) is divisible by i + 1; divide first so nothing overflows.
+                let divisor = <$t>::from(i + 1);
+                let common = gcd(u128::from(result), u128::from(divisor)) as $t;
+                (result / common).checked_mul(<$t>::from(n - i) / (divisor / common))
+            })
+        }
+    };
+}
+checked!(u64, factorial_u64, fibonacci_u64, binomial_u64);
+checked!(u128, factorial_u128, fibonacci_u128, binomial_u128);
+
+/// `n!`, exactly.
+pub fn factorial_big(n: u32) -> BigUint {
+    (1..=n).map(BigUint::from).product()
+}
+
+/// The `n`th Fibonacci number, exactly.
+pub fn fibonacci_big(n: u32) -> BigUint {
+    let (mut previous, mut current) = (BigUint::from(1u32), BigUint::from(0u32));
+    for _ in 0..n {
+        let next = &previous + &current;
+        previous = current;
+        current = next;
+    }
+    current
+}
+
+/// `n` choose `k`, exactly.
+pub fn binomial_big(n: u64, k: u64) -> BigUint {
+    if k > n {
+        return BigUint::from(0u32);
+    }
+    let k = k.min(n - k);
+    (0..k).fold(BigUint::from(1u32), |result, i| result * (n - i) / (i + 1))
+}
+
+/// A recursion that stopped before it ran out of stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooDeep {
+    /// How many levels deep it got.
+    pub depth: u32,
+}
+
+impl fmt::Display for TooDeep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "recursion stopped at depth {} before running out of stack",
+            self.depth
+        )
+    }
+}
+
+impl std::error::Error for TooDeep {}
+
+/// The address of a local variable, which is about where the stack pointer is.
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    black_box(&marker) as *const u8 as usize
+}
+
+/// Limits how much stack a recursion may use below the point where the guard was created.
+#[derive(Debug)]
+pub struct StackGuard {
+    base: usize,
+    budget: usize,
+}
+
+impl StackGuard {
+    /// A guard allowing `budget` bytes from here down. The caller must know that much stack is
+    /// left; `with_stack` makes sure of it.
+    pub fn new(budget: usize) -> StackGuard {
+        StackGuard {
+            base: stack_address(),
+            budget,
+        }
+    }
+
+    /// Fails once the stack used since `new` is over the budget.
+    pub fn check(&self, depth: u32) -> Result<(), TooDeep> {
+        if self.base.abs_diff(stack_address()) > self.budget {
+            return Err(TooDeep { depth });
+        }
+        Ok(())
+    }
+}
+
+/// Runs `f` on a thread of its own with `stack_size` bytes of stack, and a guard for all of
+/// it but a small reserve. Fails when the system can't give a thread that much stack.
+pub fn with_stack<T: Send>(
+    stack_size: usize,
+    f: impl FnOnce(&StackGuard) -> T + Send,
+) -> io::Result<T> {
+    thread::scope(|scope| {
+        let thread = thread::Builder::new()
+            .stack_size(stack_size)
+            .spawn_scoped(scope, || {
+                let reserve = RESERVE.min(stack_size / 4);
+                f(&StackGuard::new(stack_size - reserve))
+            })?;
+        Ok(thread.join().expect("The recursion panicked"))
+    })
+}
+
+/// `n!` the way `_stack_overflow_error` computes it, one call per level, but exact and
+/// stopping at the limit of `guard`.
+pub fn factorial_recursive(n: u32, guard: &StackGuard) -> Result<BigUint, TooDeep> {
+    fn factorial(n: u32, depth: u32, guard: &StackGuard) -> Result<BigUint, TooDeep> {
+        guard.check(depth)?;
+        if n == 0 {
+            return Ok(BigUint::from(1u32));
+        }
+        Ok(factorial(n - 1, depth + 1, guard)? * n)
+    }
+
+    factorial(n, 0, guard)
+}
+
+/// About the largest `n` whose `factorial_recursive` fits within `guard`; the exact limit moves
+/// by a level or so with the frames of the caller. `None` when not even `0!` fits.
+pub fn safe_depth(guard: &StackGuard) -> Option<u32> {
+    // going down costs nothing but stack; no multiplication happens before the limit is hit.
+    match factorial_recursive(u32::MAX, guard) {
+        Ok(_) => Some(u32::MAX),
+        Err(TooDeep { depth }) => depth.checked_sub(1),
+    }
+}
+
+/// The mean time of the iterative and the recursive factorial of `n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bench {
+    pub n: u32,
+    pub runs: u32,
+    pub iterative: Duration,
+    pub recursive: Result<Duration, TooDeep>,
+}
+
+/// Computes `n!` `runs` times with `factorial_big` and with `factorial_recursive`.
+pub fn benchmark(n: u32, runs: u32, guard: &StackGuard) -> Bench {
+    let runs = runs.max(1);
+
+    let started = Instant::now();
+    for _ in 0..runs {
+        black_box(factorial_big(black_box(n)));
+    }
+    let iterative = started.elapsed() / runs;
+
+    let started = Instant::now();
+    let recursive = (0..runs)
+        .try_for_each(|_| {
+            factorial_recursive(black_box(n), guard).map(|product| drop(black_box(product)))
+        })
+        .map(|()| started.elapsed() / runs);
+
+    Bench {
+        n,
+        runs,
+        iterative,
+        recursive,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_variants_stop_at_their_overflow_edge() {
+        assert_eq!(factorial_u64(20), Some(2_432_902_008_176_640_000));
+        assert_eq!(factorial_u64(21), None);
+        assert_eq!(factorial_u128(34), u128::try_from(factorial_big(34)).ok());
+        assert_eq!(factorial_u128(35), None);
+        assert_eq!(factorial_u64(0), Some(1));
+
+        assert_eq!(fibonacci_u64(93), Some(12_200_160_415_121_876_738));
+        assert_eq!(fibonacci_u64(94), None);
+        assert_eq!(fibonacci_u128(186), u128::try_from(fibonacci_big(186)).ok());
+        assert_eq!(fibonacci_u128(187), None);
+        assert_eq!(fibonacci_u64(0), Some(0));
+    }
+
+    #[test]
+    fn big_factorials_are_exact() {
+        for n in 0..=34 {
+            assert_eq!(
+                Some(factorial_big(n)),
+                factorial_u128(n).map(BigUint::from),
+                "{n}!"
+            );
+        }
+        let digits = factorial_big(10_000).to_string();
+        assert_eq!(digits.len(), 35_660);
+        assert!(digits.starts_with("28462596809170545189"));
+        // one trailing zero per factor of 5 up to 10 000: 2000 + 400 + 80 + 16 + 3.
+        let zeros = digits.len() - digits.trim_end_matches('0').len();
+        assert_eq!(zeros, 2_499);
+    }
+
+    #[test]
+    fn checked_binomials_agree_with_the_exact_ones() {
+        assert_eq!(
+            binomial_u128(68, 34),
+            u128::try_from(binomial_big(68, 34)).ok()
+        );
+        assert_eq!(
+            binomial_u64(66, 33),
+            u64::try_from(binomial_big(66, 33)).ok()
+        );
+        assert_eq!(binomial_u64(68, 34), None);
+        assert_eq!(binomial_u128(132, 66), None);
+        for n in 0..=70 {
+            for k in 0..=n + 1 {
+                let exact = binomial_big(n, k);
+                assert_eq!(binomial_u128(n, k), u128::try_from(&exact).ok(), "{n} {k}");
+                assert_eq!(binomial_u64(n, k), u64::try_from(&exact).ok(), "{n} {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn too_deep_a_recursion_is_detected_rather_than_crashing() {
+        let result = with_stack(64 * 1024, |guard| factorial_recursive(1_000_000, guard)).unwrap();
+        assert!(matches!(result, Err(TooDeep { .. })));
+    }
+
+    #[test]
+    fn a_recursion_within_the_safe_depth_is_exact() {
+        let (depth, product) = with_stack(DEFAULT_STACK, |guard| {
+            (safe_depth(guard), factorial_recursive(100, guard))
+        })
+        .unwrap();
+        assert!(depth >= Some(100));
+        assert_eq!(product, Ok(factorial_big(100)));
+    }
+}
@@ -0,0 +1,375 @@
+//! The overflow families of `scalar_types`, run side by side on one operation.
+//!
+//! Every strategy calls the standard library method of its family (`wrapping_add`,
+//! `checked_add`, ...) on the actual type. The plain operator is not called, since it would
+//! abort this program: what it does is derived from the checked result instead. A debug build
+//! panics on overflow; a release build wraps, except for division, which panics in both.
+
+use crate::int_types::{with_type, Int, IntType};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    /// The right operand is the `u32` exponent.
+    Pow,
+    /// The right operand is the `u32` number of bits to shift by.
+    Shl,
+}
+
+impl Op {
+    pub const ALL: [Op; 6] = [Op::Add, Op::Sub, Op::Mul, Op::Div, Op::Pow, Op::Shl];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Pow => "pow",
+            Op::Shl => "shl",
+        }
+    }
+
+    /// The operation as Rust code, like `127i8 + 1`.
+    pub fn expression(self, kind: IntType, lhs: Int, rhs: Int) -> String {
+        let operator = match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            // a leading minus would apply to the result of `pow`.
+            Op::Pow if lhs < Int::from(0) => return format!("({lhs}{kind}).pow({rhs})"),
+            Op::Pow => return format!("{lhs}{kind}.pow({rhs})"),
+            Op::Shl => "<<",
+        };
+        format!("{lhs}{kind} {operator} {rhs}")
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Op {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Op, String> {
+        Op::ALL
+            .into_iter()
+            .find(|op| op.name() == s)
+            .ok_or_else(|| {
+                format!("unknown operation '{s}' (expected add, sub, mul, div, pow or shl)")
+            })
+    }
+}
+
+/// The message of a panic, as the standard library words it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Panic(pub &'static str);
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "panics: {}", self.0)
+    }
+}
+
+/// What each strategy makes of one operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lab {
+    pub wrapping: Result<Int, Panic>,
+    /// `None` on overflow; the only family that never panics, not even on division by zero.
+    pub checked: Option<Int>,
+    pub overflowing: Result<(Int, bool), Panic>,
+    /// `None` when the family has no such method, as for shifts.
+    pub saturating: Option<Result<Int, Panic>>,
+    /// The plain operator in a debug build.
+    pub debug: Result<Int, Panic>,
+    /// The plain operator in a release build, where overflow checks are off.
+    pub release: Result<Int, Panic>,
+}
+
+impl Lab {
+    /// Collects the results of an operation that only overflows, never divides by zero.
+    /// `release_wraps` is false for division, whose overflow panics even in release builds.
+    fn new<T: Into<Int>>(
+        wrapping: T,
+        checked: Option<T>,
+        overflowing: (T, bool),
+        saturating: Option<T>,
+        panic: &'static str,
+        release_wraps: bool,
+    ) -> Lab {
+        let wrapping = wrapping.into();
+        let checked = checked.map(Into::into);
+        let release = match checked {
+            Some(value) => Ok(value),
+            None if release_wraps => Ok(wrapping),
+            None => Err(Panic(panic)),
+        };
+        Lab {
+            wrapping: Ok(wrapping),
+            checked,
+            overflowing: Ok((overflowing.0.into(), overflowing.1)),
+            saturating: saturating.map(|value| Ok(value.into())),
+            debug: checked.ok_or(Panic(panic)),
+            release,
+        }
+    }
+
+    fn divide_by_zero() -> Lab {
+        let panic = Panic("attempt to divide by zero");
+        Lab {
+            wrapping: Err(panic),
+            checked: None,
+            overflowing: Err(panic),
+            saturating: Some(Err(panic)),
+            debug: Err(panic),
+            release: Err(panic),
+        }
+    }
+
+    /// Whether the operation overflows (or divides by zero).
+    pub fn overflows(&self) -> bool {
+        self.checked.is_none()
+    }
+}
+
+impl fmt::Display for Lab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |result: Result<Int, Panic>| match result {
+            Ok(value) => value.to_string(),
+            Err(panic) => panic.to_string(),
+        };
+
+        writeln!(f, "{:<14} {}", "wrapping", show(self.wrapping))?;
+        let checked = match self.checked {
+            Some(value) => format!("Some({value})"),
+            None => "None".to_string(),
+        };
+        writeln!(f, "{:<14} {checked}", "checked")?;
+        let overflowing = match self.overflowing {
+            Ok((value, overflowed)) => format!("({value}, {overflowed})"),
+            Err(panic) => panic.to_string(),
+        };
+        writeln!(f, "{:<14} {overflowing}", "overflowing")?;
+        let saturating = self.saturating.map_or("no such method".to_string(), show);
+        writeln!(f, "{:<14} {saturating}", "saturating")?;
+        writeln!(f, "{:<14} {}", "debug build", show(self.debug))?;
+        let wraps = match (self.overflows(), self.release) {
+            (true, Ok(_)) => " (wraps)",
+            _ => "",
+        };
+        writeln!(f, "{:<14} {}{wraps}", "release build", show(self.release))
+    }
+}
+
+/// Runs `op` on `lhs` and `rhs` as `kind` with every strategy. Fails when an operand does not
+/// fit its type: `kind`, or `u32` for the right operand of `pow` and `shl`.
+pub fn run(op: Op, kind: IntType, lhs: Int, rhs: Int) -> Result<Lab, String> {
+    fn operand<T: TryFrom<i128> + TryFrom<u128>>(value: Int, kind: &str) -> Result<T, String> {
+        value
+            .to()
+            .ok_or_else(|| format!("{value} does not fit in {kind}"))
+    }
+
+    with_type!(kind, T => {
+        let a: T = operand(lhs, kind.name())?;
+        let lab = match op {
+            Op::Add => {
+                let b: T = operand(rhs, kind.name())?;
+                let panic = "attempt to add with overflow";
+                Lab::new(a.wrapping_add(b), a.checked_add(b), a.overflowing_add(b),
+                    Some(a.saturating_add(b)), panic, true)
+            }
+            Op::Sub => {
+                let b: T = operand(rhs, kind.name())?;
+                let panic = "attempt to subtract with overflow";
+                Lab::new(a.wrapping_sub(b), a.checked_sub(b), a.overflowing_sub(b),
+                    Some(a.saturating_sub(b)), panic, true)
+            }
+            Op::Mul => {
+                let b: T = operand(rhs, kind.name())?;
+                let panic = "attempt to multiply with overflow";
+                Lab::new(a.wrapping_mul(b), a.checked_mul(b), a.overflowing_mul(b),
+                    Some(a.saturating_mul(b)), panic, true)
+            }
+            Op::Div => {
+                let b: T = operand(rhs, kind.name())?;
+                if b == 0 {
+                    Lab::divide_by_zero()
+                } else {
+                    let panic = "attempt to divide with overflow";
+                    Lab::new(a.wrapping_div(b), a.checked_div(b), a.overflowing_div(b),
+                        Some(a.saturating_div(b)), panic, false)
+                }
+            }
+            Op::Pow => {
+                let b: u32 = operand(rhs, "u32")?;
+                let panic = "attempt to multiply with overflow";
+                Lab::new(a.wrapping_pow(b), a.checked_pow(b), a.overflowing_pow(b),
+                    Some(a.saturating_pow(b)), panic, true)
+            }
+            Op::Shl => {
+                // only a shift by the width or more overflows; bits shifted out are just lost.
+                let b: u32 = operand(rhs, "u32")?;
+                let panic = "attempt to shift left with overflow";
+                Lab::new(a.wrapping_shl(b), a.checked_shl(b), a.overflowing_shl(b), None,
+                    panic, true)
+            }
+        };
+        Ok(lab)
+    })
+}
+
+/// The cases where the strategies disagree most, and a few where they all agree, for the
+/// `overflow edge-cases` table.
+pub fn edge_cases() -> Vec<(Op, IntType, Int, Int)> {
+    vec![
+        (Op::Add, IntType::I8, Int::from(i8::MAX), Int::from(1)),
+        (Op::Add, IntType::I8, Int::from(i8::MIN), Int::from(-1)),
+        (Op::Add, IntType::U16, Int::from(u16::MAX), Int::from(1)),
+        (Op::Sub, IntType::U8, Int::from(0), Int::from(1)),
+        (Op::Sub, IntType::I16, Int::from(i16::MIN), Int::from(1)),
+        (Op::Mul, IntType::I16, Int::from(256), Int::from(128)),
+        (Op::Mul, IntType::I8, Int::from(i8::MIN), Int::from(-1)),
+        (Op::Div, IntType::I8, Int::from(i8::MIN), Int::from(-1)),
+        (Op::Div, IntType::U32, Int::from(1), Int::from(0)),
+        (Op::Div, IntType::I8, Int::from(i8::MIN), Int::from(1)),
+        (Op::Pow, IntType::U8, Int::from(2), Int::from(8)),
+        (Op::Pow, IntType::I32, Int::from(-2), Int::from(31)),
+        (Op::Pow, IntType::I32, Int::from(2), Int::from(31)),
+        (Op::Shl, IntType::U8, Int::from(1), Int::from(7)),
+        (Op::Shl, IntType::U8, Int::from(1), Int::from(8)),
+        (Op::Shl, IntType::I32, Int::from(1), Int::from(31)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(value: i128) -> Int {
+        Int::from(value)
+    }
+
+    /// A `Lab` of a case that overflows, or not, with no panic but the ones given.
+    fn lab(
+        wrapping: i128,
+        checked: Option<i128>,
+        overflowing: (i128, bool),
+        saturating: Option<i128>,
+        debug: Result<i128, Panic>,
+        release: Result<i128, Panic>,
+    ) -> Lab {
+        Lab {
+            wrapping: Ok(int(wrapping)),
+            checked: checked.map(int),
+            overflowing: Ok((int(overflowing.0), overflowing.1)),
+            saturating: saturating.map(|value| Ok(int(value))),
+            debug: debug.map(int),
+            release: release.map(int),
+        }
+    }
+
+    #[test]
+    fn every_strategy_on_the_edge_cases() {
+        let divide = Panic("attempt to divide with overflow");
+        let add = Panic("attempt to add with overflow");
+        let subtract = Panic("attempt to subtract with overflow");
+        let shift = Panic("attempt to shift left with overflow");
+        let cases = [
+            // release panics too: division overflow is always checked.
+            (
+                (Op::Div, IntType::I8, -128, -1),
+                lab(
+                    -128,
+                    None,
+                    (-128, true),
+                    Some(127),
+                    Err(divide),
+                    Err(divide),
+                ),
+            ),
+            (
+                (Op::Add, IntType::I8, 127, 1),
+                lab(-128, None, (-128, true), Some(127), Err(add), Ok(-128)),
+            ),
+            (
+                (Op::Sub, IntType::U8, 0, 1),
+                lab(255, None, (255, true), Some(0), Err(subtract), Ok(255)),
+            ),
+            // release wraps: the shift amount is masked to the width.
+            (
+                (Op::Shl, IntType::U8, 1, 8),
+                lab(1, None, (1, true), None, Err(shift), Ok(1)),
+            ),
+            (
+                (Op::Shl, IntType::U8, 1, 7),
+                lab(128, Some(128), (128, false), None, Ok(128), Ok(128)),
+            ),
+            // -2^31 is exactly i32::MIN.
+            (
+                (Op::Pow, IntType::I32, -2, 31),
+                lab(
+                    i32::MIN.into(),
+                    Some(i32::MIN.into()),
+                    (i32::MIN.into(), false),
+                    Some(i32::MIN.into()),
+                    Ok(i32::MIN.into()),
+                    Ok(i32::MIN.into()),
+                ),
+            ),
+            (
+                (Op::Div, IntType::I8, -128, 1),
+                lab(
+                    -128,
+                    Some(-128),
+                    (-128, false),
+                    Some(-128),
+                    Ok(-128),
+                    Ok(-128),
+                ),
+            ),
+        ];
+        for ((op, kind, lhs, rhs), expected) in cases {
+            let lab = run(op, kind, int(lhs), int(rhs)).unwrap();
+            assert_eq!(lab, expected, "{}", op.expression(kind, int(lhs), int(rhs)));
+        }
+    }
+
+    #[test]
+    fn dividing_by_zero_panics_everywhere_but_checked() {
+        let panic = Panic("attempt to divide by zero");
+        let expected = Lab {
+            wrapping: Err(panic),
+            checked: None,
+            overflowing: Err(panic),
+            saturating: Some(Err(panic)),
+            debug: Err(panic),
+            release: Err(panic),
+        };
+        for kind in [IntType::U32, IntType::I64] {
+            assert_eq!(run(Op::Div, kind, int(1), int(0)), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn operands_must_fit_their_types() {
+        assert!(run(Op::Add, IntType::U8, int(256), int(1)).is_err());
+        assert!(run(Op::Sub, IntType::U8, int(0), int(-1)).is_err());
+        assert!(run(Op::Pow, IntType::I32, int(2), int(-1)).is_err());
+    }
+
+    #[test]
+    fn edge_cases_fit_their_types() {
+        for (op, kind, lhs, rhs) in edge_cases() {
+            assert!(run(op, kind, lhs, rhs).is_ok(), "{op} {kind}");
+        }
+    }
+}